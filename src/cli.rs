@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
 #[command(author, version, about)]
@@ -73,6 +73,14 @@ pub enum Commands {
         /// Skip minecraft tags selection
         #[arg(long = "no-minecraft-tags", group = "minecraft_handling")]
         skip_minecraft_tags: bool,
+
+        /// Name of a user template to use for starter files that have one
+        #[arg(short = 't', long)]
+        template: Option<String>,
+
+        /// Existing datapack folder or zip to copy, renaming its namespace to the new one
+        #[arg(long, conflicts_with_all = ["minecraft", "folders", "template"])]
+        from: Option<String>,
     },
     Info {
         /// Path to datapack folder or zip file
@@ -89,6 +97,10 @@ pub enum Commands {
         /// Show only namespace information
         #[arg(long, group = "display_mode")]
         namespaces: bool,
+
+        /// Output format; json and yaml always include all information
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
     },
     Zip {
         /// Path to datapack folder
@@ -109,6 +121,23 @@ pub enum Commands {
         /// Force overwrite existing zip file without prompting
         #[arg(short = 'F', long)]
         force: bool,
+
+        /// Include files matching these patterns even if they are ignored (space-separated list)
+        #[arg(long, num_args = 1.., value_delimiter = ' ')]
+        include: Option<Vec<String>>,
+
+        /// Exclude files matching these patterns from the archive (space-separated list)
+        #[arg(long, num_args = 1.., value_delimiter = ' ')]
+        exclude: Option<Vec<String>>,
+
+        /// Build a byte-for-byte reproducible archive (fixed timestamps from SOURCE_DATE_EPOCH)
+        /// and print its SHA-256
+        #[arg(long)]
+        reproducible: bool,
+
+        /// Keep running and rebuild the archive whenever files in the datapack change
+        #[arg(short = 'w', long)]
+        watch: bool,
     },
     Add {
         /// Type of element to add (e.g., function, advancement, loot_table)
@@ -130,5 +159,223 @@ pub enum Commands {
         /// Force overwrite existing files without prompting
         #[arg(short = 'F', long)]
         force: bool,
+
+        /// Name of a template to use instead of the built-in content
+        #[arg(short, long)]
+        template: Option<String>,
+    },
+    Remove {
+        /// Type of element to remove (e.g., function, advancement, loot_table)
+        #[arg(short, long)]
+        element: Option<String>,
+
+        /// Path to datapack directory
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Name of the namespace to remove the element from
+        #[arg(short = 'x', long)]
+        namespace: Option<String>,
+
+        /// Path and name of the file to remove (supports subdirectories)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Remove without asking for confirmation
+        #[arg(short = 'F', long)]
+        force: bool,
+    },
+    Diff {
+        /// Older datapack folder or zip file
+        old: String,
+
+        /// Newer datapack folder or zip file
+        new: String,
+
+        /// Compare JSON files by content, ignoring key order and whitespace, and show a diff
+        /// of each changed JSON file
+        #[arg(long)]
+        semantic: bool,
+    },
+    #[command(visible_alias = "tree")]
+    Ls {
+        /// Path to datapack folder or zip file
+        path: Option<String>,
+
+        /// Only list elements of this registry (e.g. function, worldgen/biome) or folder (e.g. tags)
+        #[arg(short = 't', long = "type")]
+        element_type: Option<String>,
+
+        /// Only list elements of this namespace
+        #[arg(short = 'x', long)]
+        namespace: Option<String>,
+    },
+    Check {
+        /// Path to datapack folder or zip file
+        path: Option<String>,
+    },
+    Deploy {
+        /// Path to datapack folder
+        path: Option<String>,
+
+        /// World folder path, or the name of a world in the Minecraft saves folder
+        #[arg(short = 'w', long)]
+        world: Option<String>,
+
+        /// Path to the .minecraft folder used to find worlds by name
+        #[arg(short = 'm', long)]
+        minecraft_dir: Option<String>,
+
+        /// Zip the datapack and install the archive instead of copying the folder
+        #[arg(short = 'z', long, conflicts_with = "link")]
+        zip: bool,
+
+        /// Symlink the datapack folder into the world for live development
+        #[arg(short = 'l', long)]
+        link: bool,
+    },
+    RenameNamespace {
+        /// Namespace to rename
+        old: Option<String>,
+
+        /// New name for the namespace
+        new: Option<String>,
+
+        /// Path to datapack directory
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Show the files that would be moved and changed without applying anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    Mv {
+        /// Type of element to move (e.g., function, loot_table, tags/function)
+        element: Option<String>,
+
+        /// Resource location of the element to move (e.g., my_namespace:old/name)
+        from: Option<String>,
+
+        /// New resource location for the element
+        to: Option<String>,
+
+        /// Path to datapack directory
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Show the files that would be moved and changed without applying anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    Migrate {
+        /// Pack format to migrate to (e.g. 101.1); see `mcpack versions list`
+        #[arg(long)]
+        to: String,
+
+        /// Path to datapack directory
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Show the folders that would be renamed and the new pack.mcmeta without applying anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    Overlay {
+        #[command(subcommand)]
+        action: OverlayAction,
+    },
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    Versions {
+        #[command(subcommand)]
+        action: VersionsAction,
+    },
+    Unzip {
+        /// Path to datapack zip file
+        path: Option<String>,
+
+        /// Custom name for the extracted datapack folder
+        #[arg(short = 'n', long)]
+        name: Option<String>,
+
+        /// Output directory for the extracted datapack
+        #[arg(short = 'o', long)]
+        output_dir: Option<String>,
+
+        /// Force overwrite existing directory without prompting
+        #[arg(short = 'F', long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    Get {
+        /// Setting to print (description, icon, namespace, starters, format, minecraft_dir)
+        key: String,
+    },
+    Set {
+        /// Setting to change
+        key: String,
+
+        /// New value; starters and format accept a space-separated list
+        #[arg(required = true, num_args = 1..)]
+        value: Vec<String>,
+    },
+    Unset {
+        /// Setting to remove
+        key: String,
+    },
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum OverlayAction {
+    Add {
+        /// Name of the overlay folder to create in the pack root
+        directory: String,
+
+        /// Pack formats the overlay applies to as decimal values (e.g. "101.1"); provide one
+        /// for a single version, or two to define a min..max range within the pack's range
+        #[arg(short = 'f', long, required = true, num_args = 1..=2)]
+        formats: Vec<String>,
+
+        /// Path to datapack directory
+        #[arg(short, long)]
+        path: Option<String>,
+    },
+    List {
+        /// Path to datapack folder or zip file
+        path: Option<String>,
     },
+    Remove {
+        /// Name of the overlay folder to unregister
+        directory: String,
+
+        /// Path to datapack directory
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Delete the overlay folder without asking
+        #[arg(long)]
+        delete: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum VersionsAction {
+    List {
+        /// Datapack directory whose .mcpack/versions.json to include
+        #[arg(short, long)]
+        path: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Yaml,
 }
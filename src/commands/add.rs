@@ -1,9 +1,12 @@
-use crate::elements::{get_sample_content, is_valid_element_type, ELEMENT_TYPES};
+use crate::config::ProjectConfig;
+use crate::elements::{self, get_sample_content, is_valid_element_type};
+use crate::pack;
+use crate::templates::{self, TemplateContext};
 use anyhow::{Context, Result};
 use console::style;
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub fn run(command: &crate::cli::Commands) -> Result<()> {
     if let crate::cli::Commands::Add {
@@ -12,6 +15,7 @@ pub fn run(command: &crate::cli::Commands) -> Result<()> {
         namespace,
         name,
         force,
+        template,
     } = command
     {
         let theme = &ColorfulTheme::default();
@@ -21,7 +25,7 @@ pub fn run(command: &crate::cli::Commands) -> Result<()> {
             et.clone()
         } else {
             // list of available element types
-            let element_names: Vec<&str> = ELEMENT_TYPES.iter().map(|(name, _)| *name).collect();
+            let element_names = elements::element_types();
             let selection = Select::with_theme(theme)
                 .with_prompt("Select element type to add")
                 .items(&element_names)
@@ -31,12 +35,10 @@ pub fn run(command: &crate::cli::Commands) -> Result<()> {
 
         // validate element type
         if !is_valid_element_type(&element_type) {
-            let valid_types = ELEMENT_TYPES
-                .iter()
-                .map(|(name, _)| *name)
-                .collect::<Vec<_>>()
-                .join(", ");
-            anyhow::bail!("Invalid element type. Supported types are: {}", valid_types);
+            anyhow::bail!(
+                "Invalid element type. Supported types are: {}",
+                elements::element_types_hint()
+            );
         }
 
         // **Move flags_used computation before unwrapping `name`**
@@ -63,90 +65,48 @@ pub fn run(command: &crate::cli::Commands) -> Result<()> {
             anyhow::bail!("Not a datapack directory (pack.mcmeta not found)");
         }
 
-        // get or select namespace
-        let namespace = if let Some(ns) = namespace {
-            ns.clone()
-        } else {
-            // look for existing namespaces
-            let data_dir = root_dir.join("data");
-            let mut namespaces: Vec<String> = if data_dir.exists() {
-                fs::read_dir(&data_dir)?
-                    .filter_map(|entry| {
-                        entry.ok().and_then(|e| {
-                            if e.file_type().ok()?.is_dir() {
-                                Some(e.file_name().to_string_lossy().to_string())
-                            } else {
-                                None
-                            }
-                        })
-                    })
-                    .collect()
-            } else {
-                Vec::new()
-            };
-
-            // if flags are used without specifying a namespace, exclude 'minecraft' from consideration
-            if flags_used {
-                namespaces.retain(|ns| ns != "minecraft");
-            }
-
-            if namespaces.is_empty() {
-                // if no namespaces are present, prompt for namespace (including 'minecraft' if flags are used)
-                if flags_used {
-                    let mut all_namespaces = Vec::new();
-                    if data_dir.exists() {
-                        all_namespaces = fs::read_dir(&data_dir)?
-                            .filter_map(|entry| {
-                                entry.ok().and_then(|e| {
-                                    if e.file_type().ok()?.is_dir() {
-                                        Some(e.file_name().to_string_lossy().to_string())
-                                    } else {
-                                        None
-                                    }
-                                })
-                            })
-                            .collect();
-                    }
-                    // include 'minecraft' in the prompt options only if flags are used
-                    let selection = Select::with_theme(theme)
-                        .with_prompt("Select namespace to add the element to")
-                        .items(&all_namespaces)
-                        .interact()?;
-                    all_namespaces[selection].clone()
-                } else {
-                    // non-flagged command behavior
-                    Input::with_theme(theme)
-                        .with_prompt("Enter namespace name")
-                        .interact_text()?
-                }
-            } else if namespaces.len() == 1 {
-                // only one non-minecraft namespace exists, use it
-                namespaces[0].clone()
-            } else {
-                if flags_used {
-                    // multiple namespaces exist, prompt user excluding 'minecraft'
-                    let selection = Select::with_theme(theme)
-                        .with_prompt("Select namespace to add the element to")
-                        .items(&namespaces)
-                        .interact()?;
-                    namespaces[selection].clone()
+        let namespace = resolve_namespace(
+            theme,
+            &root_dir,
+            namespace.as_ref(),
+            flags_used,
+            "Select namespace to add the element to",
+        )?;
+
+        let extension = elements::element_extension(&element_type);
+
+        // prompt for a template when there are any and the command wasn't given flags
+        let template = match template {
+            Some(template) => Some(template.clone()),
+            None if !flags_used => {
+                let available =
+                    templates::list_templates(Some(&root_dir), &element_type, extension);
+                if available.is_empty() {
+                    None
                 } else {
-                    // multiple namespaces exist, include 'minecraft' in the prompt options
-                    let mut prompt_namespaces = namespaces.clone();
-                    prompt_namespaces.push("minecraft".to_string());
+                    let mut options = vec!["default".to_string()];
+                    options.extend(available);
                     let selection = Select::with_theme(theme)
-                        .with_prompt("Select namespace to add the element to")
-                        .items(&prompt_namespaces)
+                        .with_prompt("Select template to use")
+                        .items(&options)
+                        .default(0)
                         .interact()?;
-                    prompt_namespaces[selection].clone()
+                    (selection > 0).then(|| options[selection].clone())
                 }
             }
+            None => None,
         };
 
-        let (_, extension) = ELEMENT_TYPES
-            .iter()
-            .find(|(name, _)| *name == element_type)
-            .unwrap();
+        let content = match &template {
+            Some(template) => templates::render(
+                &templates::load_template(Some(&root_dir), &element_type, extension, template)?,
+                &TemplateContext {
+                    namespace: &namespace,
+                    path: &name,
+                },
+            ),
+            None => get_sample_content(&element_type),
+        };
 
         let data_type_dir = root_dir.join("data").join(&namespace).join(&element_type);
 
@@ -177,7 +137,7 @@ pub fn run(command: &crate::cli::Commands) -> Result<()> {
             }
         }
 
-        fs::write(&file_path, get_sample_content(&element_type))?;
+        fs::write(&file_path, content)?;
 
         println!(
             "\n{} Created {} '{}'",
@@ -194,3 +154,68 @@ pub fn run(command: &crate::cli::Commands) -> Result<()> {
 
     Ok(())
 }
+
+// get or select the namespace for an element, from the flag, mcpack.toml or the pack's
+// namespace folders
+pub fn resolve_namespace(
+    theme: &ColorfulTheme,
+    root_dir: &Path,
+    namespace: Option<&String>,
+    flags_used: bool,
+    prompt: &str,
+) -> Result<String> {
+    let config = ProjectConfig::load(root_dir)?;
+
+    // falling back to the project's default namespace
+    if let Some(ns) = namespace.or(config.namespace.as_ref()) {
+        return Ok(ns.clone());
+    }
+
+    // look for existing namespaces
+    let mut namespaces = pack::namespace_dirs(root_dir)?;
+
+    // if flags are used without specifying a namespace, exclude 'minecraft' from consideration
+    if flags_used {
+        namespaces.retain(|ns| ns != "minecraft");
+    }
+
+    let namespace = if namespaces.is_empty() {
+        // if no namespaces are present, prompt for namespace (including 'minecraft' if flags are used)
+        if flags_used {
+            let all_namespaces = pack::namespace_dirs(root_dir)?;
+            // include 'minecraft' in the prompt options only if flags are used
+            let selection = Select::with_theme(theme)
+                .with_prompt(prompt)
+                .items(&all_namespaces)
+                .interact()?;
+            all_namespaces[selection].clone()
+        } else {
+            // non-flagged command behavior
+            Input::with_theme(theme)
+                .with_prompt("Enter namespace name")
+                .interact_text()?
+        }
+    } else if namespaces.len() == 1 {
+        // only one non-minecraft namespace exists, use it
+        namespaces[0].clone()
+    } else {
+        if flags_used {
+            // multiple namespaces exist, prompt user excluding 'minecraft'
+            let selection = Select::with_theme(theme)
+                .with_prompt(prompt)
+                .items(&namespaces)
+                .interact()?;
+            namespaces[selection].clone()
+        } else {
+            // multiple namespaces exist, include 'minecraft' in the prompt options
+            let mut prompt_namespaces = namespaces.clone();
+            prompt_namespaces.push("minecraft".to_string());
+            let selection = Select::with_theme(theme)
+                .with_prompt(prompt)
+                .items(&prompt_namespaces)
+                .interact()?;
+            prompt_namespaces[selection].clone()
+        }
+    };
+    Ok(namespace)
+}
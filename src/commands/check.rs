@@ -0,0 +1,71 @@
+use crate::pack::{self, Pack, PackSource};
+use crate::pack_formats;
+use crate::pack_ignore::PackIgnore;
+use crate::validation::{self, Diagnostic, Severity};
+use anyhow::Result;
+use console::style;
+use std::path::Path;
+
+pub fn run(command: &crate::cli::Commands) -> Result<()> {
+    if let crate::cli::Commands::Check { path } = command {
+        let source = pack::resolve_source(path.as_deref())?;
+        pack_formats::set_project_dir(source.directory());
+        let mut pack = Pack::open(&source)?;
+        // only check what would ship, not editor or CI settings next to the pack
+        if let PackSource::Directory(dir) = &source {
+            let ignore = PackIgnore::load(dir, &[], &[])?;
+            pack.retain_files(|path| !ignore.is_ignored(Path::new(path), false));
+        }
+
+        let diagnostics = validation::validate_pack(&mut pack)?;
+
+        println!(
+            "\n{} Checking {}",
+            style("🔍").cyan(),
+            style(&pack.name).cyan().bold()
+        );
+        display_diagnostics(&diagnostics);
+
+        let errors = count_severity(&diagnostics, Severity::Error);
+        if errors > 0 {
+            anyhow::bail!("Datapack check failed with {} error(s)", errors);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn count_severity(diagnostics: &[Diagnostic], severity: Severity) -> usize {
+    diagnostics
+        .iter()
+        .filter(|d| d.severity == severity)
+        .count()
+}
+
+pub fn display_diagnostics(diagnostics: &[Diagnostic]) {
+    if diagnostics.is_empty() {
+        println!("\n{} No problems found\n", style("✓").green());
+        return;
+    }
+
+    println!();
+    for diagnostic in diagnostics {
+        let label = match diagnostic.severity {
+            Severity::Error => style("error").red().bold(),
+            Severity::Warning => style("warning").yellow().bold(),
+        };
+        println!(
+            "  {} {}: {}",
+            label,
+            style(diagnostic.location()).white(),
+            diagnostic.message
+        );
+    }
+
+    println!(
+        "\n{} {} error(s), {} warning(s)\n",
+        style("📋").cyan(),
+        count_severity(diagnostics, Severity::Error),
+        count_severity(diagnostics, Severity::Warning)
+    );
+}
@@ -0,0 +1,56 @@
+use crate::cli::ConfigAction;
+use crate::config::{self, UserConfig, USER_CONFIG_KEYS};
+use anyhow::Result;
+use console::style;
+
+pub fn run(command: &crate::cli::Commands) -> Result<()> {
+    if let crate::cli::Commands::Config { action } = command {
+        let mut user_config = UserConfig::load()?;
+
+        match action {
+            ConfigAction::Get { key } => match user_config.get(key)? {
+                Some(value) => println!("{}", value),
+                None => anyhow::bail!("'{}' is not set", key),
+            },
+            ConfigAction::Set { key, value } => {
+                user_config.set(key, value)?;
+                user_config.save()?;
+                println!(
+                    "{} Set {} to '{}'",
+                    style("✓").green(),
+                    style(key).cyan(),
+                    user_config.get(key)?.unwrap_or_default()
+                );
+            }
+            ConfigAction::Unset { key } => {
+                user_config.unset(key)?;
+                user_config.save()?;
+                println!("{} Unset {}", style("✓").green(), style(key).cyan());
+            }
+            ConfigAction::List => {
+                println!(
+                    "\n📂 {}",
+                    style(config::user_config_path()?.display().to_string())
+                        .blue()
+                        .underlined()
+                );
+                for (key, description) in USER_CONFIG_KEYS {
+                    let value = match user_config.get(key)? {
+                        Some(value) => style(value).white(),
+                        None => style("(not set)".to_string()).dim(),
+                    };
+                    println!(
+                        "  {} {} = {}  {}",
+                        style("↪").dim(),
+                        style(key).cyan(),
+                        value,
+                        style(format!("# {}", description)).dim()
+                    );
+                }
+                println!();
+            }
+        }
+    }
+
+    Ok(())
+}
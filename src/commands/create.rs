@@ -1,14 +1,19 @@
 use crate::cli::Commands;
-use crate::elements::{get_sample_content, ELEMENT_TYPES};
+use crate::config::{FormatConfig, ProjectConfig, UserConfig, PROJECT_FILE};
+use crate::elements::{self, get_sample_content};
+use crate::pack::{self, PackSource};
 use crate::pack_formats;
+use crate::pack_ignore::PackIgnore;
+use crate::rewrite;
+use crate::templates::{self, TemplateContext, PROJECT_DATA_DIR};
 use anyhow::{Context, Result};
 use console::style;
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, MultiSelect, Select};
 use rfd::FileDialog;
 use serde::Serialize;
-use serde_json;
+use serde_json::{self, Value};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug)]
 struct PackSettings {
@@ -22,6 +27,8 @@ struct PackSettings {
     minecraft_tags: Vec<String>,
     custom_namespace: Option<String>,
     custom_namespace_folders: Vec<String>,
+    template: Option<String>,
+    from: Option<PackSource>,
 }
 
 #[derive(Serialize)]
@@ -49,6 +56,14 @@ struct CreateArgs {
     skip_icon: bool,
     skip_starter_files: bool,
     skip_minecraft_tags: bool,
+    template: Option<String>,
+    from: Option<String>,
+}
+
+// what create keeps from a pack it was copied from
+struct CopiedPack {
+    mcmeta: Value,
+    config: ProjectConfig,
 }
 
 pub fn run(args: &Commands) -> Result<()> {
@@ -67,6 +82,8 @@ pub fn run(args: &Commands) -> Result<()> {
         skip_icon,
         skip_starters,
         skip_minecraft_tags,
+        template,
+        from,
     } = args
     else {
         unreachable!("create::run should only be called with Create command");
@@ -99,6 +116,8 @@ pub fn run(args: &Commands) -> Result<()> {
             skip_icon: *skip_icon,
             skip_starter_files: *skip_starters,
             skip_minecraft_tags: *skip_minecraft_tags,
+            template: template.clone(),
+            from: from.clone(),
         },
     )?;
 
@@ -107,6 +126,16 @@ pub fn run(args: &Commands) -> Result<()> {
 }
 
 fn collect_settings(theme: &ColorfulTheme, args: CreateArgs) -> Result<PackSettings> {
+    // the developer's own defaults, used in place of the built-in prompt defaults
+    let user_config = UserConfig::load()?;
+
+    // resolve the pack to copy before asking anything else
+    let from = args
+        .from
+        .as_deref()
+        .map(|path| pack::resolve_source(Some(path)))
+        .transpose()?;
+
     let name = match args.name {
         Some(name) => name,
         None => Input::with_theme(theme)
@@ -128,7 +157,12 @@ fn collect_settings(theme: &ColorfulTheme, args: CreateArgs) -> Result<PackSetti
         Some(description) => description,
         None => Input::with_theme(theme)
             .with_prompt("Datapack description")
-            .default("A newly created datapack".to_string())
+            .default(
+                user_config
+                    .description
+                    .clone()
+                    .unwrap_or_else(|| "A newly created datapack".to_string()),
+            )
             .interact_text()
             .context("Failed to get datapack description")?,
     };
@@ -141,11 +175,13 @@ fn collect_settings(theme: &ColorfulTheme, args: CreateArgs) -> Result<PackSetti
             None => {
                 let pick_icon = Confirm::with_theme(theme)
                     .with_prompt("Do you want to add a pack icon?")
-                    .default(false)
+                    .default(user_config.icon.is_some())
                     .interact()
                     .context("Failed to get icon confirmation")?;
 
-                if pick_icon {
+                if pick_icon && user_config.icon.is_some() {
+                    user_config.icon.clone()
+                } else if pick_icon {
                     let file = FileDialog::new()
                         .add_filter("PNG Image", &["png"])
                         .set_title("Select pack icon")
@@ -159,7 +195,13 @@ fn collect_settings(theme: &ColorfulTheme, args: CreateArgs) -> Result<PackSetti
         }
     };
 
-    // pack format selection
+    // pack format selection, from the versions using the decimal scheme create writes
+    let versions: Vec<&pack_formats::SupportedVersion> = pack_formats::supported_versions()
+        .iter()
+        .filter(|v| !pack_formats::is_legacy_format(v.format))
+        .collect();
+    let index_of_format = |format: [u32; 2]| versions.iter().position(|v| v.format == format);
+
     let (min_format, max_format) = match args.pack_formats {
         Some(formats) => {
             // Parse and validate each provided format string
@@ -167,7 +209,7 @@ fn collect_settings(theme: &ColorfulTheme, args: CreateArgs) -> Result<PackSetti
                 .iter()
                 .map(|s| {
                     pack_formats::parse_format_string(s).ok_or_else(|| {
-                        let supported: String = pack_formats::SUPPORTED_VERSIONS
+                        let supported: String = versions
                             .iter()
                             .map(|v| pack_formats::format_to_string(v.format))
                             .collect::<Vec<_>>()
@@ -181,8 +223,8 @@ fn collect_settings(theme: &ColorfulTheme, args: CreateArgs) -> Result<PackSetti
                 .collect::<Result<Vec<_>>>()?;
 
             for f in &parsed {
-                if !pack_formats::is_supported_format(*f) {
-                    let supported: String = pack_formats::SUPPORTED_VERSIONS
+                if index_of_format(*f).is_none() {
+                    let supported: String = versions
                         .iter()
                         .map(|v| format!("{} ({})", pack_formats::format_to_string(v.format), v.label))
                         .collect::<Vec<_>>()
@@ -197,56 +239,69 @@ fn collect_settings(theme: &ColorfulTheme, args: CreateArgs) -> Result<PackSetti
 
             let min_idx = parsed
                 .iter()
-                .map(|f| pack_formats::index_of_format(*f).unwrap())
+                .map(|f| index_of_format(*f).unwrap())
                 .min()
                 .unwrap();
             let max_idx = parsed
                 .iter()
-                .map(|f| pack_formats::index_of_format(*f).unwrap())
+                .map(|f| index_of_format(*f).unwrap())
                 .max()
                 .unwrap();
 
             (
-                pack_formats::SUPPORTED_VERSIONS[min_idx].format,
-                pack_formats::SUPPORTED_VERSIONS[max_idx].format,
+                versions[min_idx].format,
+                versions[max_idx].format,
             )
         }
         None => {
-            let items: Vec<String> = pack_formats::SUPPORTED_VERSIONS
+            let items: Vec<String> = versions
                 .iter()
                 .map(|v| format!("Minecraft {} (format {})", v.label, pack_formats::format_to_string(v.format)))
                 .collect();
 
+            // preselect the user's default range when it names known versions
+            let default_range = user_config.format_range()?;
+            let default_min = default_range
+                .and_then(|range| index_of_format(range.min))
+                .unwrap_or(0);
+
             let min_idx = Select::with_theme(theme)
                 .with_prompt("Select the minimum Minecraft version to support")
                 .items(&items)
-                .default(0)
+                .default(default_min)
                 .interact()
                 .context("Failed to select minimum version")?;
 
+            let default_max = default_range
+                .and_then(|range| index_of_format(range.max))
+                .and_then(|max_idx| max_idx.checked_sub(min_idx))
+                .unwrap_or(0);
+
             let max_items = &items[min_idx..];
             let max_offset = Select::with_theme(theme)
                 .with_prompt("Select the maximum Minecraft version to support")
                 .items(max_items)
-                .default(0)
+                .default(default_max)
                 .interact()
                 .context("Failed to select maximum version")?;
             let max_idx = min_idx + max_offset;
 
             (
-                pack_formats::SUPPORTED_VERSIONS[min_idx].format,
-                pack_formats::SUPPORTED_VERSIONS[max_idx].format,
+                versions[min_idx].format,
+                versions[max_idx].format,
             )
         }
     };
 
     // datapack settings
-    let include_minecraft_namespace = args.include_minecraft
-        || Confirm::with_theme(theme)
-            .with_prompt("Include minecraft namespace?")
-            .default(false)
-            .interact()
-            .context("Failed to get minecraft namespace confirmation")?;
+    // copied packs bring their own folders
+    let include_minecraft_namespace = from.is_none()
+        && (args.include_minecraft
+            || Confirm::with_theme(theme)
+                .with_prompt("Include minecraft namespace?")
+                .default(false)
+                .interact()
+                .context("Failed to get minecraft namespace confirmation")?);
 
     let minecraft_tags = if args.skip_minecraft_tags {
         Vec::new()
@@ -277,8 +332,12 @@ fn collect_settings(theme: &ColorfulTheme, args: CreateArgs) -> Result<PackSetti
     let custom_namespace = match args.custom_namespace {
         Some(custom_namespace) => Some(custom_namespace),
         None => loop {
-            let input: String = Input::with_theme(theme)
-                .with_prompt("Enter custom namespace")
+            let mut prompt =
+                Input::<String>::with_theme(theme).with_prompt("Enter custom namespace");
+            if let Some(namespace) = &user_config.namespace {
+                prompt = prompt.default(namespace.clone());
+            }
+            let input = prompt
                 .interact_text()
                 .context("Failed to get custom namespace")?;
 
@@ -290,21 +349,18 @@ fn collect_settings(theme: &ColorfulTheme, args: CreateArgs) -> Result<PackSetti
         },
     };
 
-    let custom_namespace_folders = if args.skip_starter_files {
+    let custom_namespace_folders = if args.skip_starter_files || from.is_some() {
         Vec::new()
     } else {
         match args.namespace_folders {
             Some(namespace_folders) => {
                 // validate each folder name
                 for folder in &namespace_folders {
-                    if !ELEMENT_TYPES.iter().any(|(name, _)| name == folder) {
+                    if !elements::is_valid_element_type(folder) {
                         anyhow::bail!(
-                            "Invalid starter folder name: '{}'. Valid options are: {:?}",
+                            "Invalid starter folder name: '{}'. Valid options are: {}",
                             folder,
-                            ELEMENT_TYPES
-                                .iter()
-                                .map(|(name, _)| name)
-                                .collect::<Vec<_>>()
+                            elements::element_types_hint()
                         );
                     }
                 }
@@ -321,16 +377,26 @@ fn collect_settings(theme: &ColorfulTheme, args: CreateArgs) -> Result<PackSetti
                         "predicate",
                     ];
 
-                    // filter ELEMENT_TYPES to include only the default folders
-                    let folder_options: Vec<&str> = ELEMENT_TYPES
+                    // offer the default folders and the user's starters
+                    let user_starters = user_config.starters.clone().unwrap_or_default();
+                    let mut folder_options: Vec<&str> = elements::element_types()
+                        .into_iter()
+                        .filter(|name| default_folders.contains(name))
+                        .collect();
+                    for starter in &user_starters {
+                        if !folder_options.contains(&starter.as_str()) {
+                            folder_options.push(starter);
+                        }
+                    }
+                    let preselected: Vec<bool> = folder_options
                         .iter()
-                        .filter(|(name, _)| default_folders.contains(name))
-                        .map(|(name, _)| *name)
+                        .map(|name| user_starters.iter().any(|starter| starter == name))
                         .collect();
 
                     let selected_folders = MultiSelect::with_theme(theme)
                         .with_prompt("Select starter folders for custom namespace")
                         .items(&folder_options)
+                        .defaults(&preselected)
                         .interact()
                         .context("Failed to select starter folders")?;
 
@@ -352,6 +418,19 @@ fn collect_settings(theme: &ColorfulTheme, args: CreateArgs) -> Result<PackSetti
         }
     };
 
+    // a template has to exist for at least one starter folder to be of any use
+    if let Some(template) = &args.template {
+        let has_template = custom_namespace_folders.iter().any(|folder| {
+            templates::list_templates(None, folder, starter_extension(folder)).contains(template)
+        });
+        if !has_template {
+            anyhow::bail!(
+                "Template '{}' not found for any of the selected starter folders",
+                template
+            );
+        }
+    }
+
     Ok(PackSettings {
         directory,
         name,
@@ -363,9 +442,15 @@ fn collect_settings(theme: &ColorfulTheme, args: CreateArgs) -> Result<PackSetti
         minecraft_tags,
         custom_namespace,
         custom_namespace_folders,
+        template: args.template,
+        from,
     })
 }
 
+fn starter_extension(element_type: &str) -> &'static str {
+    elements::element_extension(element_type)
+}
+
 fn create_pack(pack_settings: PackSettings, force: bool) -> Result<()> {
     if pack_settings.directory.exists() && !force {
         let confirm = Confirm::with_theme(&ColorfulTheme::default())
@@ -402,6 +487,16 @@ fn create_pack(pack_settings: PackSettings, force: bool) -> Result<()> {
     std::fs::create_dir_all(&pack_settings.directory)
         .context("Failed to create datapack directory")?;
 
+    // copy the source pack first, so the files generated below replace its own
+    let copied = match &pack_settings.from {
+        Some(source) => Some(copy_pack(
+            source,
+            &pack_settings.directory,
+            pack_settings.custom_namespace.as_deref(),
+        )?),
+        None => None,
+    };
+
     // handle icon if provided
     if let Some(icon_path) = pack_settings.icon_path {
         // fix the icon path by trimming surrounding quotes
@@ -439,6 +534,21 @@ fn create_pack(pack_settings: PackSettings, force: bool) -> Result<()> {
         },
     };
 
+    let mut pack_mcmeta =
+        serde_json::to_value(&pack_mcmeta).context("Failed to serialize pack.mcmeta")?;
+
+    // keep everything else a copied pack declares, such as features, filters and overlays
+    if let (Some(Value::Object(copied_mcmeta)), Value::Object(mcmeta)) = (
+        copied.as_ref().map(|copied| &copied.mcmeta),
+        &mut pack_mcmeta,
+    ) {
+        for (key, value) in copied_mcmeta {
+            if key != "pack" {
+                mcmeta.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
     let pack_mcmeta_json =
         serde_json::to_string_pretty(&pack_mcmeta).context("Failed to serialize pack.mcmeta")?;
 
@@ -448,6 +558,17 @@ fn create_pack(pack_settings: PackSettings, force: bool) -> Result<()> {
     )
     .context("Failed to write pack.mcmeta")?;
 
+    // project settings read as defaults by later commands
+    ProjectConfig {
+        namespace: pack_settings.custom_namespace.clone(),
+        format: Some(FormatConfig {
+            min: pack_formats::format_to_string(pack_settings.min_format),
+            max: pack_formats::format_to_string(pack_settings.max_format),
+        }),
+        ..copied.map(|copied| copied.config).unwrap_or_default()
+    }
+    .save(&pack_settings.directory)?;
+
     // create data folder structure
     let data_dir = pack_settings.directory.join("data");
     fs::create_dir_all(&data_dir).context("Failed to create data directory")?;
@@ -477,6 +598,28 @@ fn create_pack(pack_settings: PackSettings, force: bool) -> Result<()> {
         fs::create_dir_all(&namespace_dir)
             .with_context(|| format!("Failed to create namespace directory for {}", namespace))?;
 
+        // starter file content, taken from the chosen template where the element has one
+        let starter_content = |element_type: &str, file_name: &str| -> Result<String> {
+            let extension = starter_extension(element_type);
+            match &pack_settings.template {
+                Some(template)
+                    if templates::list_templates(None, element_type, extension)
+                        .contains(template) =>
+                {
+                    let content =
+                        templates::load_template(None, element_type, extension, template)?;
+                    Ok(templates::render(
+                        &content,
+                        &TemplateContext {
+                            namespace,
+                            path: file_name,
+                        },
+                    ))
+                }
+                _ => Ok(get_sample_content(element_type)),
+            }
+        };
+
         // create selected folders and their starter files
         for folder in &pack_settings.custom_namespace_folders {
             let folder_path = namespace_dir.join(folder);
@@ -488,43 +631,42 @@ fn create_pack(pack_settings: PackSettings, force: bool) -> Result<()> {
             match folder.as_str() {
                 "function" => {
                     let main_mcfunction = folder_path.join("main.mcfunction");
-                    fs::write(main_mcfunction, get_sample_content("function"))
+                    fs::write(main_mcfunction, starter_content("function", "main")?)
                         .context("Failed to create main.mcfunction")?;
                 }
                 "advancement" => {
                     let example_advancement = folder_path.join("advancement.json");
-                    fs::write(example_advancement, get_sample_content("advancement"))
-                        .context("Failed to create example advancement")?;
+                    fs::write(
+                        example_advancement,
+                        starter_content("advancement", "advancement")?,
+                    )
+                    .context("Failed to create example advancement")?;
                 }
                 "recipe" => {
                     let example_recipe = folder_path.join("recipe.json");
-                    fs::write(example_recipe, get_sample_content("recipe"))
+                    fs::write(example_recipe, starter_content("recipe", "recipe")?)
                         .context("Failed to create example recipe")?;
                 }
                 "loot_table" => {
                     let example_loot = folder_path.join("loot_table.json");
-                    fs::write(example_loot, get_sample_content("loot_table"))
+                    fs::write(example_loot, starter_content("loot_table", "loot_table")?)
                         .context("Failed to create example loot table")?;
                 }
                 "predicate" => {
                     let example_predicate = folder_path.join("predicate.json");
-                    fs::write(example_predicate, get_sample_content("predicate"))
-                        .context("Failed to create example predicate")?;
+                    fs::write(
+                        example_predicate,
+                        starter_content("predicate", "predicate")?,
+                    )
+                    .context("Failed to create example predicate")?;
                 }
                 // handle additional element types
                 element_type => {
                     // find the extension for the element_type
-                    if let Some((_, ext)) =
-                        ELEMENT_TYPES.iter().find(|(name, _)| *name == element_type)
-                    {
-                        let filename = format!("example{}", ext);
-                        let file_path = folder_path.join(&filename);
-                        fs::write(file_path, get_sample_content(element_type))
-                            .with_context(|| format!("Failed to create {}", filename))?;
-                    } else {
-                        // this case should not occur due to prior validation
-                        anyhow::bail!("Unsupported element type: {}", element_type);
-                    }
+                    let filename = format!("example{}", elements::element_extension(element_type));
+                    let file_path = folder_path.join(&filename);
+                    fs::write(file_path, starter_content(element_type, "example")?)
+                        .with_context(|| format!("Failed to create {}", filename))?;
                 }
             }
         }
@@ -546,3 +688,100 @@ fn create_pack(pack_settings: PackSettings, force: bool) -> Result<()> {
 
     Ok(())
 }
+
+// copy every file of an existing pack into a new pack folder, renaming its namespace
+fn copy_pack(source: &PackSource, directory: &Path, namespace: Option<&str>) -> Result<CopiedPack> {
+    let mut source_pack = pack::Pack::open(source)?;
+
+    // leave out what the source folder would leave out of an archive, but keep its
+    // project settings and templates
+    if let PackSource::Directory(dir) = source {
+        let keep = [PROJECT_FILE.to_string(), format!("{}/", PROJECT_DATA_DIR)];
+        let ignore = PackIgnore::load(dir, &keep, &[])?;
+        source_pack.retain_files(|path| !ignore.is_ignored(Path::new(path), false));
+    }
+
+    let mcmeta: Value = serde_json::from_slice(&source_pack.read("pack.mcmeta")?)
+        .with_context(|| format!("Failed to parse pack.mcmeta of {}", source_pack.name))?;
+    let config: ProjectConfig = if source_pack.contains(PROJECT_FILE) {
+        toml::from_str(&source_pack.read_to_string(PROJECT_FILE)?)
+            .with_context(|| format!("Failed to parse {} of {}", PROJECT_FILE, source_pack.name))?
+    } else {
+        ProjectConfig::default()
+    };
+
+    let old_namespace = source_namespace(&source_pack, &config)?;
+    let rename = match (&old_namespace, namespace) {
+        (Some(old), Some(new)) if old != new => Some((old.as_str(), new)),
+        _ => None,
+    };
+
+    let files = source_pack.files().to_vec();
+    for path in files {
+        // these are generated from the new pack's settings
+        if path == "pack.mcmeta" || path == PROJECT_FILE {
+            continue;
+        }
+
+        let mut content = source_pack.read(&path)?;
+        let mut target = path.clone();
+        if let Some((old, new)) = rename {
+            target = rewrite::replace_namespace_in_path(&path, old, new);
+            if rewrite::is_rewritable(&path) {
+                content = rewrite::replace_namespace(&String::from_utf8_lossy(&content), old, new)
+                    .into_bytes();
+            }
+        }
+
+        let target = directory.join(target);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(&target, content).with_context(|| format!("Failed to write {}", path))?;
+    }
+
+    match rename {
+        Some((old, new)) => println!(
+            "{} Copied {} and renamed namespace '{}' to '{}'",
+            style("✓").green(),
+            style(&source_pack.name).cyan(),
+            old,
+            new
+        ),
+        None => println!(
+            "{} Copied {}",
+            style("✓").green(),
+            style(&source_pack.name).cyan()
+        ),
+    }
+
+    Ok(CopiedPack { mcmeta, config })
+}
+
+// the namespace of a copied pack that becomes the new pack's namespace: the one named in its
+// mcpack.toml, its only custom namespace, or one chosen by the user
+fn source_namespace(source_pack: &pack::Pack, config: &ProjectConfig) -> Result<Option<String>> {
+    let namespaces = source_pack.namespaces();
+    if let Some(namespace) = &config.namespace {
+        if namespaces.contains(namespace) {
+            return Ok(Some(namespace.clone()));
+        }
+    }
+
+    let custom: Vec<String> = namespaces
+        .into_iter()
+        .filter(|namespace| namespace != "minecraft")
+        .collect();
+    match custom.len() {
+        0 => Ok(None),
+        1 => Ok(custom.into_iter().next()),
+        _ => {
+            let selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Select the namespace to rename")
+                .items(&custom)
+                .interact()?;
+            Ok(Some(custom[selection].clone()))
+        }
+    }
+}
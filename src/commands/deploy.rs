@@ -0,0 +1,252 @@
+use crate::commands::zip;
+use crate::config::UserConfig;
+use crate::pack_formats;
+use crate::pack_ignore::PackIgnore;
+use anyhow::{Context, Result};
+use console::style;
+use dialoguer::{theme::ColorfulTheme, Select};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub fn run(command: &crate::cli::Commands) -> Result<()> {
+    if let crate::cli::Commands::Deploy {
+        path,
+        world,
+        minecraft_dir,
+        zip,
+        link,
+    } = command
+    {
+        let datapack_path = match path {
+            Some(p) => PathBuf::from(p),
+            None => std::env::current_dir()?,
+        };
+
+        // verify it's a datapack directory by checking for pack.mcmeta
+        if !datapack_path.join("pack.mcmeta").exists() {
+            anyhow::bail!("Not a datapack directory (pack.mcmeta not found)");
+        }
+        let datapack_path = datapack_path
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {}", datapack_path.display()))?;
+        pack_formats::set_project_dir(Some(&datapack_path));
+        let datapack_name = datapack_path
+            .file_name()
+            .context("Invalid datapack path")?
+            .to_string_lossy()
+            .to_string();
+
+        let world_dir = resolve_world(world.as_deref(), minecraft_dir.as_deref())?;
+        let datapacks_dir = world_dir.join("datapacks");
+        fs::create_dir_all(&datapacks_dir)
+            .with_context(|| format!("Failed to create {}", datapacks_dir.display()))?;
+
+        if datapacks_dir
+            .canonicalize()
+            .is_ok_and(|dir| datapack_path.starts_with(dir))
+        {
+            anyhow::bail!("The datapack is already inside this world's datapacks folder");
+        }
+
+        let target = if *zip {
+            datapacks_dir.join(format!("{}.zip", datapack_name))
+        } else {
+            datapacks_dir.join(&datapack_name)
+        };
+
+        // build the new install under a temporary name first, so a failed copy or zip
+        // leaves the previous install in place
+        let staging = datapacks_dir.join(format!(".{}.mcpack-tmp", datapack_name));
+        if fs::symlink_metadata(&staging).is_ok() {
+            remove_entry(&staging)?;
+        }
+        let staged = if *zip {
+            zip::zip_pack(&datapack_path, &staging)
+        } else if *link {
+            symlink_dir(&datapack_path, &staging).with_context(|| {
+                format!("Failed to link datapack into {}", datapacks_dir.display())
+            })
+        } else {
+            copy_pack(&datapack_path, &staging)
+        };
+        if let Err(err) = staged {
+            if fs::symlink_metadata(&staging).is_ok() {
+                remove_entry(&staging)?;
+            }
+            return Err(err);
+        }
+
+        // remove any earlier installs of this pack, whether folders, links or archives
+        for previous in find_previous_versions(&datapacks_dir, &datapack_name)? {
+            remove_entry(&previous)?;
+            println!(
+                "{} Removed previous version: {}",
+                style("♻️").yellow(),
+                style(previous.file_name().unwrap_or_default().to_string_lossy()).dim()
+            );
+        }
+
+        fs::rename(&staging, &target)
+            .with_context(|| format!("Failed to move the datapack to {}", target.display()))?;
+
+        let world_name = world_dir
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        println!(
+            "\n{} {} '{}' to world '{}'",
+            style("✨").cyan(),
+            style(if *link { "Linked" } else { "Deployed" })
+                .green()
+                .bold(),
+            style(&datapack_name).cyan().bold(),
+            style(world_name).cyan()
+        );
+        println!(
+            "📂 {}",
+            style(target.display().to_string()).blue().underlined()
+        );
+    }
+
+    Ok(())
+}
+
+// default .minecraft folder of the vanilla launcher on each platform
+pub fn default_minecraft_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        dirs::data_dir().map(|dir| dir.join(".minecraft"))
+    } else if cfg!(target_os = "macos") {
+        dirs::data_dir().map(|dir| dir.join("minecraft"))
+    } else {
+        dirs::home_dir().map(|dir| dir.join(".minecraft"))
+    }
+}
+
+// the .minecraft folder from the flag, the MCPACK_MINECRAFT_DIR variable, the user config
+// or the platform default
+fn resolve_minecraft_dir(minecraft_dir: Option<&str>) -> Result<PathBuf> {
+    if let Some(dir) = minecraft_dir {
+        return Ok(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("MCPACK_MINECRAFT_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+    if let Some(dir) = UserConfig::load()?.minecraft_dir {
+        return Ok(PathBuf::from(dir));
+    }
+    default_minecraft_dir().context("Could not determine the .minecraft folder location")
+}
+
+// a world can be given as a path to its folder or as the name of a world in the saves folder
+fn resolve_world(world: Option<&str>, minecraft_dir: Option<&str>) -> Result<PathBuf> {
+    if let Some(world) = world {
+        let world_path = Path::new(world);
+        if world_path.join("level.dat").exists() {
+            return Ok(world_path.to_path_buf());
+        }
+    }
+
+    let saves_dir = resolve_minecraft_dir(minecraft_dir)?.join("saves");
+    if !saves_dir.is_dir() {
+        anyhow::bail!(
+            "Minecraft saves folder not found: {} (use --minecraft-dir or pass a world folder path)",
+            saves_dir.display()
+        );
+    }
+
+    let world_name = match world {
+        Some(world) => world.to_string(),
+        None => {
+            let mut worlds: Vec<String> = fs::read_dir(&saves_dir)?
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().join("level.dat").exists())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect();
+            worlds.sort();
+
+            if worlds.is_empty() {
+                anyhow::bail!("No worlds found in {}", saves_dir.display());
+            }
+
+            let selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Select world to deploy the datapack to")
+                .items(&worlds)
+                .interact()?;
+            worlds[selection].clone()
+        }
+    };
+
+    let world_dir = saves_dir.join(&world_name);
+    if !world_dir.join("level.dat").exists() {
+        anyhow::bail!(
+            "World '{}' not found in {}",
+            world_name,
+            saves_dir.display()
+        );
+    }
+    Ok(world_dir)
+}
+
+// entries in a datapacks folder that are earlier installs of a pack: a folder or link with
+// its name, or an archive named the way `mcpack zip` and `mcpack deploy --zip` name them
+fn find_previous_versions(datapacks_dir: &Path, datapack_name: &str) -> Result<Vec<PathBuf>> {
+    let mut archive_names = vec![format!("{}.zip", datapack_name)];
+    archive_names.extend(
+        pack_formats::supported_versions()
+            .iter()
+            .map(|v| format!("{}{}.zip", datapack_name, zip::version_suffix(v))),
+    );
+
+    let mut previous = Vec::new();
+    for entry in fs::read_dir(datapacks_dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if file_name == datapack_name || archive_names.contains(&file_name) {
+            previous.push(entry.path());
+        }
+    }
+    previous.sort();
+    Ok(previous)
+}
+
+fn remove_entry(path: &Path) -> Result<()> {
+    let metadata = fs::symlink_metadata(path)?;
+    let result = if metadata.is_dir() {
+        fs::remove_dir_all(path)
+    } else if metadata.file_type().is_symlink() && path.is_dir() && cfg!(windows) {
+        // directory links on windows have to be removed as directories
+        fs::remove_dir(path)
+    } else {
+        fs::remove_file(path)
+    };
+    result.with_context(|| format!("Failed to remove {}", path.display()))
+}
+
+// copy the files that would be archived, leaving out ignored files
+fn copy_pack(datapack_path: &Path, target: &Path) -> Result<()> {
+    let ignore = PackIgnore::load(datapack_path, &[], &[])?;
+    let (files, _) = zip::collect_files(datapack_path, &ignore, target)?;
+
+    for relative in files {
+        let destination = target.join(&relative);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::copy(datapack_path.join(&relative), &destination)
+            .with_context(|| format!("Failed to copy {}", relative.display()))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink_dir(source: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(source, link)
+}
+
+#[cfg(windows)]
+fn symlink_dir(source: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_dir(source, link)
+}
@@ -0,0 +1,280 @@
+use crate::pack::{self, Pack, PackSource};
+use crate::pack_formats::{self, FormatRange};
+use crate::pack_ignore::PackIgnore;
+use crate::references::{self, PackIndex};
+use crate::rewrite;
+use crate::text_component;
+use anyhow::{Context, Result};
+use console::style;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+pub fn run(command: &crate::cli::Commands) -> Result<()> {
+    if let crate::cli::Commands::Diff { old, new, semantic } = command {
+        // format labels come from the version table of the newer pack's folder
+        pack_formats::set_project_dir(pack::resolve_source(Some(new))?.directory());
+        let mut old_pack = open_pack(old)?;
+        let mut new_pack = open_pack(new)?;
+
+        println!(
+            "\n{} Comparing {} {} {}",
+            style("🔍").cyan(),
+            style(old).cyan().bold(),
+            style("→").dim(),
+            style(new).cyan().bold()
+        );
+
+        let old_mcmeta = read_mcmeta(&mut old_pack)?;
+        let new_mcmeta = read_mcmeta(&mut new_pack)?;
+        let mcmeta_changes = diff_mcmeta(&old_mcmeta, &new_mcmeta);
+
+        let old_files = index_files(&mut old_pack)?;
+        let new_files = index_files(&mut new_pack)?;
+
+        let mut added = Vec::new();
+        let mut removed = Vec::new();
+        let mut modified = Vec::new();
+        let mut diffs = Vec::new();
+
+        for (key, old_path) in &old_files {
+            let Some(new_path) = new_files.get(key) else {
+                removed.push(key);
+                continue;
+            };
+
+            let before = old_pack.read(old_path)?;
+            let after = new_pack.read(new_path)?;
+            if before == after {
+                continue;
+            }
+
+            if *semantic && old_path.ends_with(".json") && new_path.ends_with(".json") {
+                // files that only differ in formatting or key order count as unchanged
+                if let (Ok(before), Ok(after)) = (
+                    serde_json::from_slice::<Value>(&before),
+                    serde_json::from_slice::<Value>(&after),
+                ) {
+                    if before == after {
+                        continue;
+                    }
+                    diffs.push((
+                        old_path.clone(),
+                        new_path.clone(),
+                        canonical_json(&before)?,
+                        canonical_json(&after)?,
+                    ));
+                }
+            }
+            modified.push(key);
+        }
+        for key in new_files.keys() {
+            if !old_files.contains_key(key) {
+                added.push(key);
+            }
+        }
+
+        if mcmeta_changes.is_empty()
+            && added.is_empty()
+            && removed.is_empty()
+            && modified.is_empty()
+        {
+            println!("\n{} No differences found\n", style("✓").green());
+            return Ok(());
+        }
+
+        if !mcmeta_changes.is_empty() {
+            println!("\n📝 {}", style("pack.mcmeta:").yellow().bold());
+            for change in &mcmeta_changes {
+                println!("  {} {}", style("↪").dim(), change);
+            }
+        }
+
+        if !added.is_empty() || !removed.is_empty() || !modified.is_empty() {
+            println!("\n📂 {}", style("Files:").blue().bold());
+            for key in &added {
+                println!("  {} {}", style("+").green().bold(), style(key).green());
+            }
+            for key in &removed {
+                println!("  {} {}", style("-").red().bold(), style(key).red());
+            }
+            for key in &modified {
+                println!("  {} {}", style("~").yellow().bold(), style(key).yellow());
+            }
+        }
+
+        for (old_path, new_path, before, after) in &diffs {
+            rewrite::print_diff(old_path, new_path, before, after);
+        }
+
+        println!(
+            "\n{} {} added, {} removed, {} modified\n",
+            style("📋").cyan(),
+            added.len(),
+            removed.len(),
+            modified.len()
+        );
+    }
+
+    Ok(())
+}
+
+// open either side of the comparison, leaving out files a folder wouldn't ship in its zip
+fn open_pack(path: &str) -> Result<Pack> {
+    let source = pack::resolve_source(Some(path))?;
+    let mut pack = Pack::open(&source)?;
+    if let PackSource::Directory(dir) = &source {
+        let ignore = PackIgnore::load(dir, &[], &[])?;
+        pack.retain_files(|path| !ignore.is_ignored(Path::new(path), false));
+    }
+    Ok(pack)
+}
+
+fn read_mcmeta(pack: &mut Pack) -> Result<Value> {
+    let content = pack.read_to_string("pack.mcmeta")?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse pack.mcmeta of {}", pack.name))
+}
+
+// every file keyed by how it's reported: elements by registry and resource location,
+// anything else (e.g. pack.png or overlay files) by its path; renamed registry folders are
+// read under their current names, so a pack migrated past a rename compares element by element
+fn index_files(pack: &mut Pack) -> Result<BTreeMap<String, String>> {
+    let legacy_folders = references::legacy_folders(pack)?;
+    let index = PackIndex::build(pack, legacy_folders);
+    Ok(pack
+        .files()
+        .iter()
+        .filter(|path| *path != "pack.mcmeta")
+        .map(|path| {
+            let key = match index.resource_location(path) {
+                Some((registry, id)) => format!("{} {}", registry, id),
+                None => path.clone(),
+            };
+            (key, path.clone())
+        })
+        .collect())
+}
+
+// pretty printed json with object keys sorted, so diffs don't show reordered keys
+fn canonical_json(value: &Value) -> Result<String> {
+    fn sort_keys(value: &Value) -> Value {
+        match value {
+            Value::Object(obj) => {
+                let sorted: BTreeMap<&String, Value> =
+                    obj.iter().map(|(k, v)| (k, sort_keys(v))).collect();
+                Value::Object(
+                    sorted
+                        .into_iter()
+                        .map(|(k, v)| (k.clone(), v))
+                        .collect::<Map<String, Value>>(),
+                )
+            }
+            Value::Array(arr) => Value::Array(arr.iter().map(sort_keys).collect()),
+            _ => value.clone(),
+        }
+    }
+
+    let mut json = serde_json::to_string_pretty(&sort_keys(value))?;
+    json.push('\n');
+    Ok(json)
+}
+
+// human readable descriptions of what changed between two pack.mcmeta files
+fn diff_mcmeta(old: &Value, new: &Value) -> Vec<String> {
+    let mut changes = Vec::new();
+    let old_pack = old.get("pack").cloned().unwrap_or(Value::Null);
+    let new_pack = new.get("pack").cloned().unwrap_or(Value::Null);
+
+    let old_range = pack_formats::format_range_from_pack(&old_pack);
+    let new_range = pack_formats::format_range_from_pack(&new_pack);
+    if old_range != new_range {
+        changes.push(format!(
+            "Format range: {} {} {}",
+            format_range_label(old_range),
+            style("→").dim(),
+            format_range_label(new_range)
+        ));
+    }
+
+    if old_pack.get("description") != new_pack.get("description") {
+        let text = |pack: &Value| {
+            text_component::plain_text(pack.get("description").unwrap_or(&Value::Null))
+        };
+        changes.push(format!(
+            "Description: \"{}\" {} \"{}\"",
+            text(&old_pack),
+            style("→").dim(),
+            text(&new_pack)
+        ));
+    }
+
+    let old_features = enabled_features(old);
+    let new_features = enabled_features(new);
+    for feature in new_features.difference(&old_features) {
+        changes.push(format!("Feature enabled: {}", style(feature).green()));
+    }
+    for feature in old_features.difference(&new_features) {
+        changes.push(format!("Feature disabled: {}", style(feature).red()));
+    }
+
+    if old.get("filter") != new.get("filter") {
+        changes.push("File filters changed".to_string());
+    }
+
+    let old_overlays = overlay_entries(old);
+    let new_overlays = overlay_entries(new);
+    for (directory, entry) in &new_overlays {
+        match old_overlays.get(directory) {
+            None => changes.push(format!("Overlay added: {}", style(directory).green())),
+            Some(old_entry) if old_entry != entry => {
+                changes.push(format!("Overlay changed: {}", style(directory).yellow()))
+            }
+            Some(_) => {}
+        }
+    }
+    for directory in old_overlays.keys() {
+        if !new_overlays.contains_key(directory) {
+            changes.push(format!("Overlay removed: {}", style(directory).red()));
+        }
+    }
+
+    changes
+}
+
+fn format_range_label(range: Option<FormatRange>) -> String {
+    match range {
+        Some(range) => format!("{} ({})", range, range.version_labels()),
+        None => "none".to_string(),
+    }
+}
+
+fn enabled_features(mcmeta: &Value) -> BTreeSet<String> {
+    mcmeta
+        .pointer("/features/enabled")
+        .and_then(|e| e.as_array())
+        .map(|features| {
+            features
+                .iter()
+                .filter_map(|f| f.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// overlay entries keyed by directory
+fn overlay_entries(mcmeta: &Value) -> BTreeMap<String, Value> {
+    mcmeta
+        .pointer("/overlays/entries")
+        .and_then(|e| e.as_array())
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| {
+                    let directory = entry.get("directory")?.as_str()?;
+                    Some((directory.to_string(), entry.clone()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
@@ -1,51 +1,83 @@
 use anyhow::{Context, Result};
 use console::style;
+use serde::{Serialize, Serializer};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fs;
-use std::io::Read;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use walkdir::WalkDir;
-use zip::ZipArchive;
 
-use crate::pack_formats;
+use crate::cli::OutputFormat;
+use crate::config::{ProjectConfig, PROJECT_FILE};
+use crate::pack::{self, Pack, PackSource};
+use crate::pack_formats::{self, FormatRange};
+use crate::registries;
+use crate::text_component;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 struct DatapackInfo {
     name: String,
-    description: String,
-    format_range: Option<([u32; 2], [u32; 2])>,
-    namespaces: HashMap<String, NamespaceInfo>,
-    features: Vec<(String, bool)>,
+    description: DescriptionInfo,
+    #[serde(serialize_with = "serialize_format_range")]
+    format_range: Option<FormatRange>,
+    // range the project targets in mcpack.toml, when it has one
+    #[serde(
+        serialize_with = "serialize_format_range",
+        skip_serializing_if = "Option::is_none"
+    )]
+    target_format_range: Option<FormatRange>,
+    namespaces: BTreeMap<String, NamespaceInfo>,
+    features: Vec<FeatureInfo>,
     filter: Option<FilterInfo>,
     overlays: Vec<OverlayInfo>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+struct DescriptionInfo {
+    raw: Value,
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+struct FeatureInfo {
+    name: String,
+    known: bool,
+}
+
+#[derive(Debug, Serialize)]
 struct FilterInfo {
     block: Vec<BlockPattern>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 struct BlockPattern {
     namespace: Option<String>,
     path: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 struct OverlayInfo {
-    formats: Vec<u8>,
+    // none when the entry's formats can't be read
+    #[serde(serialize_with = "serialize_format_range")]
+    formats: Option<FormatRange>,
     directory: String,
+    // whether the overlay applies to formats the pack itself doesn't support
+    outside_pack_range: bool,
 }
 
-#[derive(Debug, Default)]
+// format range as written to json/yaml output
+#[derive(Serialize)]
+struct FormatRangeOutput {
+    min: String,
+    max: String,
+    versions: String,
+    supported: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
 struct NamespaceInfo {
-    functions: usize,
-    advancements: usize,
-    recipes: usize,
-    loot_tables: usize,
-    predicates: usize,
-    tags: usize,
+    // file count per registry folder, e.g. "function", "worldgen/biome" or "tags/block"
+    registries: BTreeMap<String, usize>,
     world_gen: bool,
 }
 
@@ -55,138 +87,73 @@ pub fn run(command: &crate::cli::Commands) -> Result<()> {
         compact,
         pack_info,
         namespaces,
+        format,
     } = command
     {
-        match path {
-            Some(input_path) => {
-                let path_obj = Path::new(input_path);
-
-                // check if it's a directory (datapack folder)
-                if path_obj.is_dir() {
-                    let pack_mcmeta = path_obj.join("pack.mcmeta");
-                    if !pack_mcmeta.exists() {
-                        anyhow::bail!(
-                            "Not a valid datapack directory: pack.mcmeta not found in {}",
-                            input_path
-                        );
-                    }
-                    let info = collect_info(&pack_mcmeta)?;
-                    display_info(&info, *compact, *pack_info, *namespaces);
-                }
-                // if it's not a directory, check if it's a zip file
-                else if path_obj.exists() && input_path.ends_with(".zip") {
-                    let file = fs::File::open(path_obj)
-                        .with_context(|| format!("Failed to open zip file: {}", input_path))?;
-                    let mut archive = ZipArchive::new(file)
-                        .with_context(|| format!("Failed to read zip archive: {}", input_path))?;
-
-                    let pack_mcmeta_content = find_pack_mcmeta_in_zip(&mut archive)?;
-                    let info =
-                        collect_info_from_zip(&pack_mcmeta_content, &mut archive, input_path)?;
-                    display_info(&info, *compact, *pack_info, *namespaces);
-                }
-                // if neither exists, try appending .zip to the path
-                else {
-                    let mut zip_path = String::from(input_path);
-                    if !zip_path.ends_with(".zip") {
-                        zip_path.push_str(".zip");
-                    }
-
-                    let zip_path_obj = Path::new(&zip_path);
-                    if zip_path_obj.exists() {
-                        let file = fs::File::open(zip_path_obj)
-                            .with_context(|| format!("Failed to open zip file: {}", zip_path))?;
-                        let mut archive = ZipArchive::new(file)
-                            .with_context(|| format!("Failed to read zip archive: {}", zip_path))?;
-
-                        let pack_mcmeta_content = find_pack_mcmeta_in_zip(&mut archive)?;
-                        let info =
-                            collect_info_from_zip(&pack_mcmeta_content, &mut archive, &zip_path)?;
-                        display_info(&info, *compact, *pack_info, *namespaces);
-                    } else {
-                        anyhow::bail!(
-                            "Neither datapack folder '{}' nor zip file '{}' found",
-                            input_path,
-                            zip_path
-                        );
-                    }
-                }
+        let source = pack::resolve_source(path.as_deref())?;
+        pack_formats::set_project_dir(source.directory());
+        let info = match source {
+            PackSource::Directory(dir) => {
+                let mut info = collect_info(&dir.join("pack.mcmeta"))?;
+                info.target_format_range = ProjectConfig::load(&dir)?.format_range()?;
+                info
             }
-            None => {
-                let pack_mcmeta = PathBuf::from("pack.mcmeta");
-                if !pack_mcmeta.exists() {
-                    anyhow::bail!("Not in a datapack directory (pack.mcmeta not found)");
-                }
-                let info = collect_info(&pack_mcmeta)?;
-                display_info(&info, *compact, *pack_info, *namespaces);
-            }
-        }
-    }
-
-    Ok(())
-}
-
-// locate pack.mcmeta in the zip archive
-fn find_pack_mcmeta_in_zip(archive: &mut ZipArchive<fs::File>) -> Result<String> {
-    let mut pack_mcmeta_content = None;
+            PackSource::Zip(_) => collect_info_from_zip(&mut Pack::open(&source)?)?,
+        };
 
-    for i in 0..archive.len() {
-        let mut file = archive.by_index(i)?;
-        let name = file.name().to_string();
-
-        if name == "pack.mcmeta" || name.ends_with("/pack.mcmeta") {
-            let mut content = String::new();
-            file.read_to_string(&mut content)?;
-            pack_mcmeta_content = Some(content);
-            break;
+        match format {
+            OutputFormat::Text => display_info(&info, *compact, *pack_info, *namespaces),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&info)?),
+            OutputFormat::Yaml => print!("{}", serde_norway::to_string(&info)?),
         }
     }
 
-    pack_mcmeta_content.context("pack.mcmeta not found in zip archive")
+    Ok(())
 }
 
 // parse the description field in pack.mcmeta if it's a string or using json chat components
 fn parse_description(desc: &Value) -> String {
-    match desc {
-        Value::String(s) => s.to_string(),
-        Value::Array(arr) => arr
-            .iter()
-            .map(|component| parse_text_component(component))
-            .collect::<String>(),
-        Value::Object(obj) => parse_text_component(&Value::Object(obj.clone())),
-        _ => "Invalid description".to_string(),
+    if !matches!(desc, Value::String(_) | Value::Array(_) | Value::Object(_)) {
+        return "Invalid description".to_string();
     }
+
+    let mut description = String::new();
+    text_component::visit_text(desc, None, &mut |text, color| {
+        let text = match color {
+            None | Some("") => text.to_string(),
+            Some("gray") => style(text).dim().to_string(),
+            Some(_) => style(text).color256(24).to_string(),
+        };
+        description.push_str(&text);
+    });
+    description
 }
 
-// parse a chat component object into a string
-fn parse_text_component(component: &Value) -> String {
-    match component {
-        Value::String(s) => s.to_string(),
-        Value::Object(obj) => {
-            if let Some(text) = obj.get("text").and_then(|t| t.as_str()) {
-                let color = obj.get("color").and_then(|c| c.as_str()).unwrap_or("");
-                match color {
-                    "" => text.to_string(),
-                    c if c.starts_with('#') => style(text).color256(24).to_string(),
-                    "gray" => style(text).dim().to_string(),
-                    _ => style(text).color256(24).to_string(),
-                }
-            } else {
-                String::new()
-            }
-        }
-        _ => String::new(),
-    }
+fn parse_description_info(pack: &Value) -> DescriptionInfo {
+    let raw = pack
+        .get("description")
+        .cloned()
+        .unwrap_or(Value::String("".into()));
+    let text = text_component::plain_text(&raw);
+    DescriptionInfo { raw, text }
 }
 
-fn parse_format_range(pack: &Value) -> Option<([u32; 2], [u32; 2])> {
-    let min = pack_formats::format_from_json(pack.get("min_format")?)?;
-    let max = pack_formats::format_from_json(pack.get("max_format")?)?;
-    Some((min, max))
+fn serialize_format_range<S: Serializer>(
+    range: &Option<FormatRange>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    range
+        .map(|range| FormatRangeOutput {
+            min: pack_formats::format_to_string(range.min),
+            max: pack_formats::format_to_string(range.max),
+            versions: range.version_labels(),
+            supported: range.is_supported(),
+        })
+        .serialize(serializer)
 }
 
 // parse the features field in pack.mcmeta
-fn parse_features(mcmeta: &Value) -> Vec<(String, bool)> {
+fn parse_features(mcmeta: &Value) -> Vec<FeatureInfo> {
     let valid_features = vec![
         "minecraft:redstone_experiments",
         "minecraft:minecart_improvements",
@@ -201,10 +168,10 @@ fn parse_features(mcmeta: &Value) -> Vec<(String, bool)> {
     {
         for feature in features_arr {
             if let Some(feature_str) = feature.as_str() {
-                features.push((
-                    feature_str.to_string(),
-                    valid_features.contains(&feature_str),
-                ));
+                features.push(FeatureInfo {
+                    name: feature_str.to_string(),
+                    known: valid_features.contains(&feature_str),
+                });
             }
         }
     }
@@ -238,8 +205,8 @@ fn parse_filter(mcmeta: &Value) -> Option<FilterInfo> {
         .filter(|f| !f.block.is_empty())
 }
 
-// parse the overlays field in pack.mcmeta
-fn parse_overlays(mcmeta: &Value) -> Vec<OverlayInfo> {
+// parse the overlays field in pack.mcmeta, with ranges in any of the overlay entry schemes
+fn parse_overlays(mcmeta: &Value, pack_range: Option<FormatRange>) -> Vec<OverlayInfo> {
     let mut overlays = Vec::new();
     if let Some(entries) = mcmeta
         .get("overlays")
@@ -247,113 +214,68 @@ fn parse_overlays(mcmeta: &Value) -> Vec<OverlayInfo> {
         .and_then(|e| e.as_array())
     {
         for entry in entries {
-            if let Some(obj) = entry.as_object() {
-                let formats = match obj.get("formats") {
-                    Some(Value::Array(arr)) => arr
-                        .iter()
-                        .filter_map(|v| v.as_u64())
-                        .map(|v| v as u8)
-                        .collect(),
-                    Some(Value::Number(n)) => vec![n.as_u64().unwrap_or_default() as u8],
-                    _ => Vec::new(),
-                };
-                let directory = obj
-                    .get("directory")
-                    .and_then(|d| d.as_str())
-                    .unwrap_or("")
-                    .to_string();
-                if !formats.is_empty() && !directory.is_empty() {
-                    overlays.push(OverlayInfo { formats, directory });
-                }
+            let directory = entry
+                .get("directory")
+                .and_then(|d| d.as_str())
+                .unwrap_or("")
+                .to_string();
+            if directory.is_empty() {
+                continue;
             }
+            let formats = pack_formats::format_range_from_overlay(entry);
+            let outside_pack_range = match (pack_range, formats) {
+                (Some(pack_range), Some(formats)) => !pack_range.contains(&formats),
+                _ => false,
+            };
+            overlays.push(OverlayInfo {
+                formats,
+                directory,
+                outside_pack_range,
+            });
         }
     }
     overlays
 }
 
-// collect all the information from a zip archive
-fn collect_info_from_zip(
-    pack_mcmeta_content: &str,
-    archive: &mut ZipArchive<fs::File>,
-    zip_path: &str,
-) -> Result<DatapackInfo> {
-    let mcmeta: Value =
-        serde_json::from_str(pack_mcmeta_content).context("Failed to parse pack.mcmeta")?;
+// collect all the information from a zip archive, which may hold the pack in a top-level
+// folder
+fn collect_info_from_zip(pack: &mut Pack) -> Result<DatapackInfo> {
+    let mcmeta: Value = serde_json::from_str(&pack.read_to_string("pack.mcmeta")?)
+        .context("Failed to parse pack.mcmeta")?;
 
-    let pack = mcmeta
+    let pack_obj = mcmeta
         .get("pack")
         .context("Invalid pack.mcmeta: missing 'pack' object")?;
 
-    let format_range = parse_format_range(pack);
-    let description =
-        parse_description(pack.get("description").unwrap_or(&Value::String("".into())));
-    let name = Path::new(zip_path)
-        .file_stem()
-        .unwrap_or_default()
-        .to_string_lossy()
-        .to_string();
+    let format_range = pack_formats::format_range_from_pack(pack_obj);
+    let description = parse_description_info(pack_obj);
+    let name = pack.name.clone();
 
     let features = parse_features(&mcmeta);
     let filter = parse_filter(&mcmeta);
-    let overlays = parse_overlays(&mcmeta);
-
-    let mut namespaces = HashMap::new();
-    let mut current_namespace = String::new();
-    let mut current_info = NamespaceInfo::default();
-
-    for i in 0..archive.len() {
-        let file = archive.by_index(i)?;
-        let path = file.name();
-
-        if let Some(data_path) = path.strip_prefix("data/") {
-            let parts: Vec<&str> = data_path.split('/').collect();
-            if parts.len() >= 2 {
-                let namespace = parts[0].to_string();
+    let overlays = parse_overlays(&mcmeta, format_range);
+    let legacy_folders = registries::legacy_folders(format_range);
 
-                if !current_namespace.is_empty() && namespace != current_namespace {
-                    if current_info.has_content() {
-                        namespaces.insert(current_namespace.clone(), current_info);
-                    }
-                    current_info = NamespaceInfo::default();
-                }
-
-                current_namespace = namespace;
-
-                if path.contains("/worldgen/") {
-                    current_info.world_gen = true;
-                }
+    let mut namespaces: BTreeMap<String, NamespaceInfo> = BTreeMap::new();
 
-                match parts.last() {
-                    Some(filename) if filename.ends_with(".mcfunction") => {
-                        current_info.functions += 1
-                    }
-                    Some(filename) if filename.ends_with(".json") => {
-                        if path.contains("/advancement/") {
-                            current_info.advancements += 1;
-                        } else if path.contains("/recipe/") {
-                            current_info.recipes += 1;
-                        } else if path.contains("/loot_table/") {
-                            current_info.loot_tables += 1;
-                        } else if path.contains("/predicate/") {
-                            current_info.predicates += 1;
-                        } else if path.contains("/tags/") {
-                            current_info.tags += 1;
-                        }
-                    }
-                    _ => {}
-                }
-            }
+    for path in pack.files() {
+        if let Some((namespace, relative)) =
+            path.strip_prefix("data/").and_then(|p| p.split_once('/'))
+        {
+            namespaces
+                .entry(namespace.to_string())
+                .or_default()
+                .add_file(relative, &legacy_folders);
         }
     }
 
-    if !current_namespace.is_empty() && current_info.has_content() {
-        namespaces.insert(current_namespace, current_info);
-    }
+    namespaces.retain(|_, info| info.has_content());
 
     Ok(DatapackInfo {
         name,
         description,
         format_range,
+        target_format_range: None,
         namespaces,
         features,
         filter,
@@ -369,9 +291,8 @@ fn collect_info(pack_mcmeta_path: &Path) -> Result<DatapackInfo> {
         .get("pack")
         .context("Invalid pack.mcmeta: missing 'pack' object")?;
 
-    let format_range = parse_format_range(pack);
-    let description =
-        parse_description(pack.get("description").unwrap_or(&Value::String("".into())));
+    let format_range = pack_formats::format_range_from_pack(pack);
+    let description = parse_description_info(pack);
 
     // Get the datapack name from the parent directory of pack.mcmeta
     let datapack_dir = pack_mcmeta_path.parent().unwrap_or(Path::new("."));
@@ -392,17 +313,18 @@ fn collect_info(pack_mcmeta_path: &Path) -> Result<DatapackInfo> {
 
     let features = parse_features(&mcmeta);
     let filter = parse_filter(&mcmeta);
-    let overlays = parse_overlays(&mcmeta);
+    let overlays = parse_overlays(&mcmeta, format_range);
+    let legacy_folders = registries::legacy_folders(format_range);
 
     let data_dir = datapack_dir.join("data");
-    let mut namespaces = HashMap::new();
+    let mut namespaces = BTreeMap::new();
 
     if data_dir.exists() {
         for entry in fs::read_dir(&data_dir)? {
             let entry = entry?;
             if entry.file_type()?.is_dir() {
                 let namespace = entry.file_name().to_string_lossy().to_string();
-                let namespace_info = collect_namespace_info(&entry.path())?;
+                let namespace_info = collect_namespace_info(&entry.path(), &legacy_folders)?;
                 if namespace_info.has_content() {
                     namespaces.insert(namespace, namespace_info);
                 }
@@ -414,6 +336,7 @@ fn collect_info(pack_mcmeta_path: &Path) -> Result<DatapackInfo> {
         name,
         description,
         format_range,
+        target_format_range: None,
         namespaces,
         features,
         filter,
@@ -423,17 +346,29 @@ fn collect_info(pack_mcmeta_path: &Path) -> Result<DatapackInfo> {
 
 impl NamespaceInfo {
     fn has_content(&self) -> bool {
-        self.functions > 0
-            || self.advancements > 0
-            || self.recipes > 0
-            || self.loot_tables > 0
-            || self.predicates > 0
-            || self.tags > 0
-            || self.world_gen
+        !self.registries.is_empty()
+    }
+
+    // count a file by its registry folder, given its path relative to the namespace folder;
+    // renamed folders are counted under their current registry
+    fn add_file(&mut self, relative: &str, legacy_folders: &BTreeMap<String, String>) {
+        if relative.starts_with("worldgen/") {
+            self.world_gen = true;
+        }
+
+        let relative = &*registries::current_folder_path(relative, legacy_folders);
+        if let Some(registry) = registries::registry_for_path(relative) {
+            if relative.ends_with(registries::extension_for_registry(&registry)) {
+                *self.registries.entry(registry).or_default() += 1;
+            }
+        }
     }
 }
 
-fn collect_namespace_info(namespace_path: &Path) -> Result<NamespaceInfo> {
+fn collect_namespace_info(
+    namespace_path: &Path,
+    legacy_folders: &BTreeMap<String, String>,
+) -> Result<NamespaceInfo> {
     let mut info = NamespaceInfo::default();
 
     for entry in WalkDir::new(namespace_path)
@@ -444,34 +379,14 @@ fn collect_namespace_info(namespace_path: &Path) -> Result<NamespaceInfo> {
             continue;
         }
 
-        let path = entry.path();
-        let relative = path
+        let relative = entry
+            .path()
             .strip_prefix(namespace_path)
             .unwrap()
             .to_string_lossy()
-            .to_string();
-
-        if relative.starts_with("worldgen/") {
-            info.world_gen = true;
-        }
+            .replace('\\', "/");
 
-        match path.extension().and_then(|s| s.to_str()) {
-            Some("mcfunction") => info.functions += 1,
-            Some("json") => {
-                if relative.starts_with("advancement/") {
-                    info.advancements += 1;
-                } else if relative.starts_with("recipe/") {
-                    info.recipes += 1;
-                } else if relative.starts_with("loot_table/") {
-                    info.loot_tables += 1;
-                } else if relative.starts_with("predicate/") {
-                    info.predicates += 1;
-                } else if relative.starts_with("tags/") {
-                    info.tags += 1;
-                }
-            }
-            _ => {}
-        }
+        info.add_file(&relative, legacy_folders);
     }
 
     Ok(info)
@@ -484,33 +399,24 @@ fn display_info(info: &DatapackInfo, compact: bool, pack_info: bool, namespaces_
         style("📦").cyan(),
         style(&info.name).cyan().bold()
     );
-    println!("{}", style(&info.description).italic());
+    println!(
+        "{}",
+        style(parse_description(&info.description.raw)).italic()
+    );
 
     // always show pack format info
     match &info.format_range {
-        Some((min, max))
-            if pack_formats::is_supported_format(*min)
-                && pack_formats::is_supported_format(*max)
-                && pack_formats::cmp_format(*min, *max) != std::cmp::Ordering::Greater =>
+        Some(range)
+            if range.is_supported()
+                && pack_formats::cmp_format(range.min, range.max)
+                    != std::cmp::Ordering::Greater =>
         {
-            let single = min == max;
-            let formats_str = if single {
-                pack_formats::format_to_string(*min)
-            } else {
-                format!(
-                    "{} - {}",
-                    pack_formats::format_to_string(*min),
-                    pack_formats::format_to_string(*max)
-                )
-            };
-            let versions_str = pack_formats::version_label_range(*min, *max);
-
             println!(
                 "\n{} Pack Format{}: {} ({})",
                 "📝",
-                if single { "" } else { "s" },
-                style(formats_str).green().bold(),
-                style(versions_str).yellow()
+                if range.min == range.max { "" } else { "s" },
+                style(range).green().bold(),
+                style(range.version_labels()).yellow()
             );
         }
         _ => {
@@ -522,6 +428,18 @@ fn display_info(info: &DatapackInfo, compact: bool, pack_info: bool, namespaces_
         }
     }
 
+    // pack.mcmeta drifting from the range the project targets is usually a mistake
+    if let Some(target) = info.target_format_range {
+        if info.format_range != Some(target) {
+            println!(
+                "{} pack.mcmeta does not match the format range in {} ({})",
+                style("⚠️").yellow(),
+                PROJECT_FILE,
+                target
+            );
+        }
+    }
+
     // return early if compact mode
     if compact {
         println!();
@@ -532,11 +450,11 @@ fn display_info(info: &DatapackInfo, compact: bool, pack_info: bool, namespaces_
     if !namespaces_only {
         if !info.features.is_empty() {
             println!("\n{} {}", "🔧", style("Enabled Features:").yellow().bold());
-            for (feature, is_valid) in &info.features {
-                let feature_style = if *is_valid {
-                    style(feature)
+            for feature in &info.features {
+                let feature_style = if feature.known {
+                    style(&feature.name)
                 } else {
-                    style(feature).red()
+                    style(&feature.name).red()
                 };
                 println!("  {} {}", style("↪").dim(), feature_style);
             }
@@ -566,17 +484,22 @@ fn display_info(info: &DatapackInfo, compact: bool, pack_info: bool, namespaces_
         if !info.overlays.is_empty() {
             println!("\n{} {}", "📎", style("Overlays:").magenta().bold());
             for overlay in &info.overlays {
-                println!(
-                    "  {} {} (formats: {})",
-                    style("↪").dim(),
-                    overlay.directory,
-                    overlay
-                        .formats
-                        .iter()
-                        .map(|f| f.to_string())
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                );
+                let formats = match overlay.formats {
+                    Some(range) => format!(
+                        "formats: {}, {}",
+                        range,
+                        style(pack_formats::version_label_range(range.min, range.max)).yellow()
+                    ),
+                    None => style("unrecognised formats").red().to_string(),
+                };
+                println!("  {} {} ({})", style("↪").dim(), overlay.directory, formats);
+                if overlay.outside_pack_range {
+                    println!(
+                        "    {} {}",
+                        style("⚠️").yellow(),
+                        style("applies to formats outside the pack's own range").yellow()
+                    );
+                }
             }
         }
     }
@@ -591,23 +514,13 @@ fn display_info(info: &DatapackInfo, compact: bool, pack_info: bool, namespaces_
                 style(namespace).white()
             );
 
-            if info.functions > 0 {
-                println!("  {} Functions: {}", style("↪").dim(), info.functions);
-            }
-            if info.advancements > 0 {
-                println!("  {} Advancements: {}", style("↪").dim(), info.advancements);
-            }
-            if info.recipes > 0 {
-                println!("  {} Recipes: {}", style("↪").dim(), info.recipes);
-            }
-            if info.loot_tables > 0 {
-                println!("  {} Loot Tables: {}", style("↪").dim(), info.loot_tables);
-            }
-            if info.predicates > 0 {
-                println!("  {} Predicates: {}", style("↪").dim(), info.predicates);
-            }
-            if info.tags > 0 {
-                println!("  {} Tags: {}", style("↪").dim(), info.tags);
+            for (registry, count) in &info.registries {
+                println!(
+                    "  {} {}: {}",
+                    style("↪").dim(),
+                    registries::label_for_registry(registry),
+                    count
+                );
             }
 
             if info.world_gen {
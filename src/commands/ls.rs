@@ -0,0 +1,90 @@
+use crate::pack::{self, Pack};
+use crate::pack_formats;
+use crate::references::{self, PackIndex};
+use crate::registries;
+use anyhow::Result;
+use console::style;
+use std::collections::BTreeMap;
+
+pub fn run(command: &crate::cli::Commands) -> Result<()> {
+    if let crate::cli::Commands::Ls {
+        path,
+        element_type,
+        namespace,
+    } = command
+    {
+        let source = pack::resolve_source(path.as_deref())?;
+        pack_formats::set_project_dir(source.directory());
+        let mut pack = Pack::open(&source)?;
+        // packs for older formats may still use renamed registry folders
+        let legacy_folders = references::legacy_folders(&mut pack)?;
+        let index = PackIndex::build(&pack, legacy_folders);
+
+        // namespace -> registry -> resource locations, all sorted
+        let mut tree: BTreeMap<String, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+        for file in pack.files() {
+            let Some((registry, id)) = index.resource_location(file) else {
+                continue;
+            };
+            let (ns, _) = id.split_once(':').unwrap_or_default();
+
+            if namespace.as_ref().is_some_and(|wanted| wanted != ns) {
+                continue;
+            }
+            // a folder such as "tags" or "worldgen" matches every registry inside it
+            if element_type.as_ref().is_some_and(|wanted| {
+                registry != *wanted && !registry.starts_with(&format!("{}/", wanted))
+            }) {
+                continue;
+            }
+
+            tree.entry(ns.to_string())
+                .or_default()
+                .entry(registry)
+                .or_default()
+                .push(id);
+        }
+
+        println!(
+            "\n{} {}",
+            style("📦").cyan(),
+            style(&pack.name).cyan().bold()
+        );
+
+        if tree.is_empty() {
+            println!("\n{} No elements found\n", style("⚠️").yellow());
+            return Ok(());
+        }
+
+        let mut total = 0;
+        for (ns, registries) in &tree {
+            println!(
+                "\n📂 {} {}",
+                style("Namespace:").blue().bold(),
+                style(ns).white()
+            );
+
+            for (registry, ids) in registries {
+                println!(
+                    "  {} {}",
+                    style(registries::label_for_registry(registry)).yellow(),
+                    style(format!("({})", ids.len())).dim()
+                );
+                // tags are referred to with a leading '#'
+                let prefix = if registry.starts_with("tags/") {
+                    "#"
+                } else {
+                    ""
+                };
+                for id in ids {
+                    println!("    {} {}{}", style("↪").dim(), prefix, id);
+                }
+                total += ids.len();
+            }
+        }
+
+        println!("\n{} {} element(s)\n", style("📋").cyan(), total);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,216 @@
+use crate::config::{FormatConfig, ProjectConfig};
+use crate::pack::{self, Pack, PackSource};
+use crate::pack_formats::{self, FormatRange};
+use crate::rewrite::{self, FileChange};
+use anyhow::{Context, Result};
+use console::style;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+// pack.mcmeta fields that declare the supported formats, replaced as a whole when migrating
+const FORMAT_KEYS: &[&str] = &[
+    "pack_format",
+    "supported_formats",
+    "min_format",
+    "max_format",
+];
+
+pub fn run(command: &crate::cli::Commands) -> Result<()> {
+    if let crate::cli::Commands::Migrate { path, to, dry_run } = command {
+        let root_dir = match path {
+            Some(p) => PathBuf::from(p),
+            None => std::env::current_dir()?,
+        };
+
+        // verify it's a datapack directory
+        if !root_dir.join("pack.mcmeta").exists() {
+            anyhow::bail!("Not a datapack directory (pack.mcmeta not found)");
+        }
+        pack_formats::set_project_dir(Some(&root_dir));
+
+        let target = pack_formats::parse_format_string(to)
+            .with_context(|| format!("Invalid pack format: '{}'", to))?;
+        if !pack_formats::is_supported_format(target) {
+            anyhow::bail!(
+                "Pack format '{}' is not a known version. Run 'mcpack versions list' to see every known format",
+                pack_formats::format_to_string(target)
+            );
+        }
+
+        let mut pack = Pack::open(&PackSource::Directory(root_dir.clone()))?;
+        pack.retain_files(|path| !pack::is_vcs_path(path));
+
+        let mcmeta_content = pack.read_to_string("pack.mcmeta")?;
+        let mut mcmeta: Value =
+            serde_json::from_str(&mcmeta_content).context("Failed to parse pack.mcmeta")?;
+        let current = mcmeta
+            .get("pack")
+            .and_then(pack_formats::format_range_from_pack)
+            .context("Could not read the pack format declared in pack.mcmeta")?;
+
+        if pack_formats::cmp_format(target, current.max) == std::cmp::Ordering::Less {
+            anyhow::bail!(
+                "The pack already supports formats up to {}; migrate can only upgrade",
+                pack_formats::format_to_string(current.max)
+            );
+        }
+
+        // every version released after the pack's oldest format, up to the target
+        let versions: Vec<&pack_formats::SupportedVersion> = pack_formats::supported_versions()
+            .iter()
+            .filter(|v| v.format > current.min && v.format <= target)
+            .collect();
+
+        let mut renames = BTreeMap::new();
+        for version in &versions {
+            renames.extend(version.renames.clone());
+        }
+
+        // versions before the last rename can't read the renamed folders anymore
+        let min = versions
+            .iter()
+            .filter(|v| !v.renames.is_empty())
+            .map(|v| v.format)
+            .max()
+            .map_or(current.min, |format| format.max(current.min));
+        let migrated = FormatRange { min, max: target };
+
+        let mut changes = plan_folder_renames(&pack, &renames)?;
+        let moved = changes.len();
+
+        write_format_range(&mut mcmeta, migrated)?;
+        let mut new_content = serde_json::to_string_pretty(&mcmeta)?;
+        if mcmeta_content.ends_with('\n') {
+            new_content.push('\n');
+        }
+        if new_content != mcmeta_content {
+            changes.push(FileChange {
+                path: "pack.mcmeta".to_string(),
+                new_path: "pack.mcmeta".to_string(),
+                content: Some((mcmeta_content, new_content)),
+            });
+        }
+
+        if changes.is_empty() {
+            println!(
+                "\n{} Nothing to migrate, the pack already targets {}\n",
+                style("✓").green(),
+                style(migrated).cyan()
+            );
+            return Ok(());
+        }
+
+        if *dry_run {
+            println!(
+                "\n{} Migrating from {} to {} ({}) would move {} file(s):\n",
+                style("🔍").cyan(),
+                style(current).cyan(),
+                style(migrated).cyan(),
+                migrated.version_labels(),
+                moved
+            );
+            rewrite::print_changes(&changes);
+            println!();
+            return Ok(());
+        }
+
+        rewrite::apply_changes(&root_dir, &changes)?;
+
+        // keep the project's target range in step with pack.mcmeta
+        let mut config = ProjectConfig::load(&root_dir)?;
+        if config.format.is_some() {
+            config.format = Some(FormatConfig {
+                min: pack_formats::format_to_string(migrated.min),
+                max: pack_formats::format_to_string(migrated.max),
+            });
+            config.save(&root_dir)?;
+        }
+
+        println!(
+            "\n{} Migrated from {} to {} ({}), {} file(s) moved",
+            style("✓").green(),
+            style(current).cyan(),
+            style(migrated).cyan(),
+            migrated.version_labels(),
+            moved
+        );
+    }
+
+    Ok(())
+}
+
+// move files of renamed registry folders in data/, e.g. data/ns/functions/ to data/ns/function/;
+// overlays are left alone since they usually target the versions that used the old names
+fn plan_folder_renames(pack: &Pack, renames: &BTreeMap<String, String>) -> Result<Vec<FileChange>> {
+    let mut changes = Vec::new();
+    for path in pack.files() {
+        let Some((namespace, relative)) = path
+            .strip_prefix("data/")
+            .and_then(|rest| rest.split_once('/'))
+        else {
+            continue;
+        };
+
+        let Some(new_relative) = renames.iter().find_map(|(old, new)| {
+            let rest = relative.strip_prefix(old.as_str())?.strip_prefix('/')?;
+            Some(format!("{}/{}", new, rest))
+        }) else {
+            continue;
+        };
+
+        let new_path = format!("data/{}/{}", namespace, new_relative);
+        if pack.contains(&new_path) {
+            anyhow::bail!(
+                "Cannot move {} to {}: the file already exists",
+                path,
+                new_path
+            );
+        }
+        changes.push(FileChange {
+            path: path.clone(),
+            new_path,
+            content: None,
+        });
+    }
+    Ok(changes)
+}
+
+// replace the format fields of pack.mcmeta with the scheme the range needs: min_format and
+// max_format for decimal formats, plus pack_format and supported_formats for legacy versions
+fn write_format_range(mcmeta: &mut Value, range: FormatRange) -> Result<()> {
+    let pack = mcmeta
+        .get_mut("pack")
+        .and_then(|p| p.as_object_mut())
+        .context("Invalid pack.mcmeta: missing 'pack' object")?;
+
+    // rebuild the object so the remaining fields keep their order
+    let mut migrated: Map<String, Value> = pack
+        .iter()
+        .filter(|(key, _)| !FORMAT_KEYS.contains(&key.as_str()))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+
+    if pack_formats::is_legacy_format(range.min) {
+        migrated.insert("pack_format".to_string(), range.min[0].into());
+        if range.min != range.max {
+            migrated.insert(
+                "supported_formats".to_string(),
+                Value::from(vec![range.min[0], range.max[0]]),
+            );
+        }
+    }
+    if !pack_formats::is_legacy_format(range.max) {
+        migrated.insert(
+            "min_format".to_string(),
+            pack_formats::format_to_json(range.min),
+        );
+        migrated.insert(
+            "max_format".to_string(),
+            pack_formats::format_to_json(range.max),
+        );
+    }
+
+    *pack = migrated;
+    Ok(())
+}
@@ -1,4 +1,16 @@
 pub mod add;
+pub mod check;
+pub mod config;
 pub mod create;
+pub mod deploy;
+pub mod diff;
 pub mod info;
+pub mod ls;
+pub mod migrate;
+pub mod mv;
+pub mod overlay;
+pub mod remove;
+pub mod rename_namespace;
+pub mod unzip;
+pub mod versions;
 pub mod zip;
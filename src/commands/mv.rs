@@ -0,0 +1,189 @@
+use crate::config::ProjectConfig;
+use crate::pack::{self, Pack, PackSource};
+use crate::pack_formats;
+use crate::references::{self, PackIndex};
+use crate::registries;
+use crate::resource_location;
+use crate::rewrite;
+use anyhow::{Context, Result};
+use console::style;
+use dialoguer::{theme::ColorfulTheme, Input, Select};
+use std::path::{Path, PathBuf};
+
+pub fn run(command: &crate::cli::Commands) -> Result<()> {
+    if let crate::cli::Commands::Mv {
+        element,
+        from,
+        to,
+        path,
+        dry_run,
+    } = command
+    {
+        let theme = &ColorfulTheme::default();
+
+        let root_dir = match path {
+            Some(p) => PathBuf::from(p),
+            None => std::env::current_dir()?,
+        };
+
+        // verify it's a datapack directory
+        if !root_dir.join("pack.mcmeta").exists() {
+            anyhow::bail!("Not a datapack directory (pack.mcmeta not found)");
+        }
+
+        // references are rewritten across the whole folder, leaving version control folders alone
+        pack_formats::set_project_dir(Some(&root_dir));
+        let mut pack = Pack::open(&PackSource::Directory(root_dir.clone()))?;
+        pack.retain_files(|path| !pack::is_vcs_path(path));
+        // packs for older formats may still use renamed registry folders
+        let legacy_folders = references::legacy_folders(&mut pack)?;
+        let index = PackIndex::build(&pack, legacy_folders);
+
+        // prompt for the element type from the ones the pack has
+        let registry = match element {
+            Some(element) => element.clone(),
+            None => {
+                let mut present: Vec<String> = pack
+                    .files()
+                    .iter()
+                    .filter_map(|path| index.resource_location(path))
+                    .map(|(registry, _)| registry)
+                    .collect();
+                present.sort();
+                present.dedup();
+                if present.is_empty() {
+                    anyhow::bail!("The datapack has no elements to move");
+                }
+                let selection = Select::with_theme(theme)
+                    .with_prompt("Select element type to move")
+                    .items(&present)
+                    .interact()?;
+                present[selection].clone()
+            }
+        };
+
+        if !registries::is_registry(&registry) {
+            anyhow::bail!(
+                "Unknown element type '{}'. Use a registry folder name such as function, loot_table or tags/function",
+                registry
+            );
+        }
+
+        let from = match from {
+            Some(from) => from.clone(),
+            None => Input::with_theme(theme)
+                .with_prompt("Enter the resource location to move")
+                .interact_text()
+                .context("Failed to get resource location")?,
+        };
+        let to = match to {
+            Some(to) => to.clone(),
+            None => Input::with_theme(theme)
+                .with_prompt("Enter the new resource location")
+                .interact_text()
+                .context("Failed to get resource location")?,
+        };
+
+        // ids without a namespace use the project's namespace, and the target defaults to
+        // the source's namespace
+        let old_id = qualify_id(&root_dir, &from, None)?;
+        let (old_namespace, _) = resource_location::split(&old_id);
+        let new_id = qualify_id(&root_dir, &to, Some(old_namespace))?;
+        for id in [&old_id, &new_id] {
+            if !resource_location::is_valid(id) {
+                anyhow::bail!("Invalid resource location '{}'", id);
+            }
+        }
+        if old_id == new_id {
+            anyhow::bail!("The source and target are the same");
+        }
+
+        // references are told apart by their command or JSON field where possible, so one
+        // to an element of another type with the same id may still be rewritten elsewhere
+        let shared_id: Vec<String> = pack
+            .files()
+            .iter()
+            .filter_map(|path| index.resource_location(path))
+            .filter(|(other, id)| {
+                *id == old_id
+                    && *other != registry
+                    && other.starts_with("tags/") == registry.starts_with("tags/")
+            })
+            .map(|(other, _)| other)
+            .collect();
+        if !shared_id.is_empty() {
+            println!(
+                "{} '{}' is also used by: {}. References whose command or field doesn't tell them apart may be rewritten as well, check with --dry-run",
+                style("⚠️").yellow(),
+                old_id,
+                shared_id.join(", ")
+            );
+        }
+
+        let changes = rewrite::plan_element_move(&mut pack, &index, &registry, &old_id, &new_id)?;
+        let updated = changes
+            .iter()
+            .filter(|c| c.content.is_some() && !c.is_move())
+            .count();
+
+        if *dry_run {
+            println!(
+                "\n{} Moving {} '{}' to '{}' would update {} other file(s):\n",
+                style("🔍").cyan(),
+                registry,
+                style(&old_id).cyan(),
+                style(&new_id).cyan(),
+                updated
+            );
+            rewrite::print_changes(&changes);
+            println!();
+            return Ok(());
+        }
+
+        rewrite::apply_changes(&root_dir, &changes)?;
+
+        println!(
+            "\n{} Moved {} '{}' to '{}' ({} other file(s) updated)",
+            style("✓").green(),
+            registry,
+            style(&old_id).cyan(),
+            style(&new_id).cyan(),
+            updated
+        );
+    }
+
+    Ok(())
+}
+
+// fully qualify an id given on the command line
+fn qualify_id(root_dir: &Path, input: &str, namespace: Option<&str>) -> Result<String> {
+    if input.contains(':') {
+        return Ok(resource_location::normalize(input));
+    }
+
+    let namespace = match namespace {
+        Some(namespace) => namespace.to_string(),
+        None => default_namespace(root_dir)?.with_context(|| {
+            format!(
+                "Include the namespace in '{}' (e.g. my_namespace:{})",
+                input, input
+            )
+        })?,
+    };
+    Ok(format!("{}:{}", namespace, input))
+}
+
+// the project's namespace, or the only custom namespace of the pack
+fn default_namespace(root_dir: &Path) -> Result<Option<String>> {
+    if let Some(namespace) = ProjectConfig::load(root_dir)?.namespace {
+        return Ok(Some(namespace));
+    }
+    let custom: Vec<String> = pack::namespace_dirs(root_dir)?
+        .into_iter()
+        .filter(|namespace| namespace != "minecraft")
+        .collect();
+    Ok(match custom.as_slice() {
+        [namespace] => Some(namespace.clone()),
+        _ => None,
+    })
+}
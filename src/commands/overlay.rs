@@ -0,0 +1,356 @@
+use crate::cli::OverlayAction;
+use crate::pack::{self, Pack, PackSource};
+use crate::pack_formats::{self, FormatRange};
+use anyhow::{Context, Result};
+use console::style;
+use dialoguer::{theme::ColorfulTheme, Confirm};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub fn run(command: &crate::cli::Commands) -> Result<()> {
+    if let crate::cli::Commands::Overlay { action } = command {
+        match action {
+            OverlayAction::Add {
+                directory,
+                formats,
+                path,
+            } => add_overlay(&pack_root(path.as_deref())?, directory, formats)?,
+            OverlayAction::List { path } => list_overlays(path.as_deref())?,
+            OverlayAction::Remove {
+                directory,
+                path,
+                delete,
+            } => remove_overlay(&pack_root(path.as_deref())?, directory, *delete)?,
+        }
+    }
+
+    Ok(())
+}
+
+fn pack_root(path: Option<&str>) -> Result<PathBuf> {
+    let root_dir = match path {
+        Some(p) => PathBuf::from(p),
+        None => std::env::current_dir()?,
+    };
+
+    // verify it's a datapack directory
+    if !root_dir.join("pack.mcmeta").exists() {
+        anyhow::bail!("Not a datapack directory (pack.mcmeta not found)");
+    }
+    pack_formats::set_project_dir(Some(&root_dir));
+    Ok(root_dir)
+}
+
+fn add_overlay(root_dir: &Path, directory: &str, formats: &[String]) -> Result<()> {
+    if directory.is_empty()
+        || !directory
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+    {
+        anyhow::bail!(
+            "Invalid overlay directory '{}'. Only lowercase letters, digits, '_' and '-' are allowed",
+            directory
+        );
+    }
+    if directory == "data" {
+        anyhow::bail!("The overlay directory can't be named 'data'");
+    }
+
+    let mut parsed = formats
+        .iter()
+        .map(|s| {
+            pack_formats::parse_format_string(s).with_context(|| {
+                format!(
+                    "Invalid pack format: '{}'. Must be a decimal format (e.g. 101.1)",
+                    s
+                )
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    parsed.sort();
+    let range = FormatRange {
+        min: parsed[0],
+        max: parsed[parsed.len() - 1],
+    };
+
+    let (content, mut mcmeta) = read_mcmeta(root_dir)?;
+    let pack_range = mcmeta
+        .get("pack")
+        .and_then(pack_formats::format_range_from_pack)
+        .context("Could not read the pack format declared in pack.mcmeta")?;
+    if !pack_range.contains(&range) {
+        anyhow::bail!(
+            "Overlay formats {} are outside the pack's own range ({})",
+            range,
+            pack_range
+        );
+    }
+
+    let entries = overlay_entries_mut(&mut mcmeta)?;
+    if entries
+        .iter()
+        .any(|entry| entry.get("directory").and_then(|d| d.as_str()) == Some(directory))
+    {
+        anyhow::bail!(
+            "Overlay '{}' is already registered in pack.mcmeta",
+            directory
+        );
+    }
+
+    let mut entry = Map::new();
+    entry.insert("directory".to_string(), directory.into());
+    // versions before the decimal scheme read the legacy formats field instead
+    if pack_formats::is_legacy_format(range.min) {
+        entry.insert(
+            "formats".to_string(),
+            Value::from(vec![range.min[0], range.max[0]]),
+        );
+    }
+    entry.insert(
+        "min_format".to_string(),
+        pack_formats::format_to_json(range.min),
+    );
+    entry.insert(
+        "max_format".to_string(),
+        pack_formats::format_to_json(range.max),
+    );
+    entries.push(Value::Object(entry));
+
+    let overlay_dir = root_dir.join(directory).join("data");
+    fs::create_dir_all(&overlay_dir)
+        .with_context(|| format!("Failed to create {}", overlay_dir.display()))?;
+    write_mcmeta(root_dir, &content, &mcmeta)?;
+
+    println!(
+        "\n{} Added overlay '{}' for formats {} ({})",
+        style("✓").green(),
+        style(directory).cyan(),
+        style(range).green().bold(),
+        style(range.version_labels()).yellow()
+    );
+    println!(
+        "  {} Files in {} replace the matching files in data/ for these versions\n",
+        style("↪").dim(),
+        style(format!("{}/data/", directory)).white()
+    );
+
+    Ok(())
+}
+
+fn list_overlays(path: Option<&str>) -> Result<()> {
+    let source = pack::resolve_source(path)?;
+    pack_formats::set_project_dir(source.directory());
+    let mut pack = Pack::open(&source)?;
+    let mcmeta: Value = serde_json::from_str(&pack.read_to_string("pack.mcmeta")?)
+        .context("Failed to parse pack.mcmeta")?;
+    let pack_range = mcmeta
+        .get("pack")
+        .and_then(pack_formats::format_range_from_pack);
+
+    let entries = mcmeta
+        .pointer("/overlays/entries")
+        .and_then(|e| e.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    println!(
+        "\n{} {}",
+        style("📦").cyan(),
+        style(&pack.name).cyan().bold()
+    );
+
+    if entries.is_empty() {
+        println!("\n{} No overlays registered\n", style("⚠️").yellow());
+        return Ok(());
+    }
+
+    println!("\n📎 {}", style("Overlays:").magenta().bold());
+    for entry in &entries {
+        let directory = entry
+            .get("directory")
+            .and_then(|d| d.as_str())
+            .unwrap_or("");
+        let range = pack_formats::format_range_from_overlay(entry);
+
+        let formats = match range {
+            Some(range) => format!(
+                "{} ({})",
+                style(range).green(),
+                style(range.version_labels()).yellow()
+            ),
+            None => style("unrecognised formats").red().to_string(),
+        };
+        println!(
+            "  {} {} {}",
+            style("↪").dim(),
+            style(directory).white().bold(),
+            formats
+        );
+
+        if let (Some(range), Some(pack_range)) = (range, pack_range) {
+            if !pack_range.contains(&range) {
+                println!(
+                    "    {} outside the pack's own range ({})",
+                    style("⚠️").yellow(),
+                    pack_range
+                );
+            }
+        }
+
+        let exists = match &source {
+            PackSource::Directory(dir) => dir.join(directory).is_dir(),
+            PackSource::Zip(_) => {
+                let prefix = format!("{}/", directory);
+                pack.files().iter().any(|f| f.starts_with(&prefix))
+            }
+        };
+        if !exists {
+            println!(
+                "    {} the '{}' folder does not exist",
+                style("⚠️").yellow(),
+                directory
+            );
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+fn remove_overlay(root_dir: &Path, directory: &str, delete: bool) -> Result<()> {
+    let (content, mut mcmeta) = read_mcmeta(root_dir)?;
+
+    let entries = overlay_entries_mut(&mut mcmeta)?;
+    let count = entries.len();
+    entries.retain(|entry| entry.get("directory").and_then(|d| d.as_str()) != Some(directory));
+    if entries.len() == count {
+        anyhow::bail!("Overlay '{}' is not registered in pack.mcmeta", directory);
+    }
+
+    // drop the overlays object entirely once nothing is left in it
+    if entries.is_empty() {
+        if let Some(obj) = mcmeta.as_object_mut() {
+            let only_entries = obj
+                .get("overlays")
+                .and_then(|o| o.as_object())
+                .is_some_and(|o| o.len() == 1);
+            if only_entries {
+                obj.shift_remove("overlays");
+            }
+        }
+    }
+
+    write_mcmeta(root_dir, &content, &mcmeta)?;
+    println!(
+        "\n{} Removed overlay '{}' from pack.mcmeta",
+        style("✓").green(),
+        style(directory).cyan()
+    );
+
+    let overlay_dir = root_dir.join(directory);
+    if overlay_dir.is_dir() {
+        let delete = delete
+            || Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Delete the '{}' folder too?", directory))
+                .default(false)
+                .interact()?;
+        if delete {
+            fs::remove_dir_all(&overlay_dir)
+                .with_context(|| format!("Failed to remove {}", overlay_dir.display()))?;
+            println!(
+                "{} Deleted folder '{}'",
+                style("✓").green(),
+                overlay_dir.display()
+            );
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+fn read_mcmeta(root_dir: &Path) -> Result<(String, Value)> {
+    let content =
+        fs::read_to_string(root_dir.join("pack.mcmeta")).context("Failed to read pack.mcmeta")?;
+    let mcmeta = serde_json::from_str(&content).context("Failed to parse pack.mcmeta")?;
+    Ok((content, mcmeta))
+}
+
+// write pack.mcmeta back, keeping a trailing newline if the original had one
+fn write_mcmeta(root_dir: &Path, original: &str, mcmeta: &Value) -> Result<()> {
+    let mut content =
+        serde_json::to_string_pretty(mcmeta).context("Failed to serialize pack.mcmeta")?;
+    if original.ends_with('\n') {
+        content.push('\n');
+    }
+    fs::write(root_dir.join("pack.mcmeta"), content).context("Failed to write pack.mcmeta")
+}
+
+// the overlays.entries array of pack.mcmeta, created if it's missing
+fn overlay_entries_mut(mcmeta: &mut Value) -> Result<&mut Vec<Value>> {
+    let obj = mcmeta
+        .as_object_mut()
+        .context("Invalid pack.mcmeta: not a JSON object")?;
+    let overlays = obj
+        .entry("overlays")
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+        .context("Invalid pack.mcmeta: 'overlays' is not an object")?;
+    overlays
+        .entry("entries")
+        .or_insert_with(|| Value::Array(Vec::new()))
+        .as_array_mut()
+        .context("Invalid pack.mcmeta: 'overlays.entries' is not an array")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pack_with(pack: Value) -> tempfile::TempDir {
+        let root = tempfile::tempdir().unwrap();
+        let mcmeta = json!({ "pack": pack });
+        fs::write(root.path().join("pack.mcmeta"), mcmeta.to_string()).unwrap();
+        root
+    }
+
+    fn formats(formats: &[&str]) -> Vec<String> {
+        formats.iter().map(|f| f.to_string()).collect()
+    }
+
+    #[test]
+    fn adds_overlays_inside_a_major_only_max_format() {
+        let root = pack_with(json!({"description": "", "min_format": 101, "max_format": 107}));
+
+        add_overlay(root.path(), "late", &formats(&["101.0", "107.1"])).unwrap();
+
+        let (_, mcmeta) = read_mcmeta(root.path()).unwrap();
+        assert_eq!(
+            mcmeta["overlays"]["entries"],
+            json!([{"directory": "late", "min_format": [101, 0], "max_format": [107, 1]}])
+        );
+        assert!(root.path().join("late/data").is_dir());
+    }
+
+    #[test]
+    fn rejects_overlays_outside_the_pack_range() {
+        let root = pack_with(json!({"description": "", "min_format": 101, "max_format": 107}));
+
+        for outside in [&["100.0", "101.0"][..], &["107.0", "108.0"][..]] {
+            let err = add_overlay(root.path(), "o", &formats(outside)).unwrap_err();
+            assert!(err.to_string().contains("outside the pack's own range"));
+        }
+        assert!(!root.path().join("o").exists());
+    }
+
+    #[test]
+    fn checks_exact_bounds_of_a_decimal_range() {
+        let root =
+            pack_with(json!({"description": "", "min_format": [88, 0], "max_format": [94, 1]}));
+
+        assert!(add_overlay(root.path(), "too_new", &formats(&["94.2"])).is_err());
+        add_overlay(root.path(), "newest", &formats(&["94.1"])).unwrap();
+    }
+}
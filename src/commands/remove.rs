@@ -0,0 +1,168 @@
+use crate::commands::add;
+use crate::elements::{self, is_valid_element_type};
+use crate::pack::{Pack, PackSource};
+use crate::pack_ignore::PackIgnore;
+use crate::rewrite;
+use anyhow::{Context, Result};
+use console::style;
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub fn run(command: &crate::cli::Commands) -> Result<()> {
+    if let crate::cli::Commands::Remove {
+        element,
+        path,
+        namespace,
+        name,
+        force,
+    } = command
+    {
+        let theme = &ColorfulTheme::default();
+
+        // prompt for element_type if not provided
+        let element_type = if let Some(et) = element {
+            et.clone()
+        } else {
+            let element_names = elements::element_types();
+            let selection = Select::with_theme(theme)
+                .with_prompt("Select element type to remove")
+                .items(&element_names)
+                .interact()?;
+            element_names[selection].to_string()
+        };
+
+        if !is_valid_element_type(&element_type) {
+            anyhow::bail!(
+                "Invalid element type. Supported types are: {}",
+                elements::element_types_hint()
+            );
+        }
+
+        let flags_used = element.is_some() || name.is_some();
+
+        // prompt for name if not provided
+        let name = if let Some(n) = name {
+            n.clone()
+        } else {
+            Input::with_theme(theme)
+                .with_prompt("Enter name of the file to remove")
+                .interact_text()
+                .context("Failed to get file name")?
+        };
+
+        let root_dir = if let Some(p) = path {
+            PathBuf::from(p)
+        } else {
+            std::env::current_dir()?
+        };
+
+        // verify it's a datapack directory
+        if !root_dir.join("pack.mcmeta").exists() {
+            anyhow::bail!("Not a datapack directory (pack.mcmeta not found)");
+        }
+
+        let namespace = add::resolve_namespace(
+            theme,
+            &root_dir,
+            namespace.as_ref(),
+            flags_used,
+            "Select namespace to remove the element from",
+        )?;
+
+        let extension = elements::element_extension(&element_type);
+
+        let relative = format!(
+            "data/{}/{}/{}{}",
+            namespace,
+            element_type,
+            name.trim_end_matches(extension),
+            extension
+        );
+        let file_path = root_dir.join(&relative);
+        if !file_path.is_file() {
+            anyhow::bail!("{} '{}' not found ({})", element_type, name, relative);
+        }
+
+        let id = format!("{}:{}", namespace, name.trim_end_matches(extension));
+        let is_tag = element_type.starts_with("tags/");
+        let references = find_references(&root_dir, &relative, &id, is_tag)?;
+
+        if !references.is_empty() {
+            println!(
+                "\n{} {} reference(s) to '{}{}' will be left dangling:",
+                style("⚠️").yellow(),
+                references.len(),
+                if is_tag { "#" } else { "" },
+                style(&id).cyan()
+            );
+            for (location, line) in &references {
+                println!(
+                    "  {} {}: {}",
+                    style("↪").dim(),
+                    style(location).white(),
+                    style(line).dim()
+                );
+            }
+            println!();
+        }
+
+        if !*force {
+            let confirm = Confirm::with_theme(theme)
+                .with_prompt(format!("Remove {} '{}'?", element_type, id))
+                .default(references.is_empty())
+                .interact()?;
+
+            if !confirm {
+                println!(
+                    "{} {}",
+                    style("✗").red(),
+                    style("Operation cancelled").bold()
+                );
+                return Ok(());
+            }
+        }
+
+        fs::remove_file(&file_path)
+            .with_context(|| format!("Failed to remove {}", file_path.display()))?;
+        rewrite::remove_empty_parents(&root_dir.join("data"), &file_path)?;
+
+        println!(
+            "\n{} Removed {} '{}'",
+            style("✓").green(),
+            style(&element_type).cyan(),
+            style(&relative).white()
+        );
+    }
+
+    Ok(())
+}
+
+// every line in the pack's JSON and mcfunction files that refers to a resource location,
+// as ("path:line", line text)
+fn find_references(
+    root_dir: &Path,
+    removed: &str,
+    id: &str,
+    is_tag: bool,
+) -> Result<Vec<(String, String)>> {
+    let mut pack = Pack::open(&PackSource::Directory(root_dir.to_path_buf()))?;
+    let ignore = PackIgnore::load(root_dir, &[], &[])?;
+    pack.retain_files(|path| {
+        path != removed
+            && rewrite::is_rewritable(path)
+            && !ignore.is_ignored(Path::new(path), false)
+    });
+
+    let mut references = Vec::new();
+    let files = pack.files().to_vec();
+    for path in files {
+        let content = String::from_utf8_lossy(&pack.read(&path)?).to_string();
+        let lines: Vec<&str> = content.lines().collect();
+        for line in rewrite::find_resource_location(&content, id, is_tag) {
+            let text = lines.get(line - 1).map(|l| l.trim()).unwrap_or_default();
+            references.push((format!("{}:{}", path, line), text.to_string()));
+        }
+    }
+    Ok(references)
+}
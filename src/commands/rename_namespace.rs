@@ -0,0 +1,118 @@
+use crate::config::ProjectConfig;
+use crate::pack::{self, Pack, PackSource};
+use crate::resource_location;
+use crate::rewrite;
+use anyhow::{Context, Result};
+use console::style;
+use dialoguer::{theme::ColorfulTheme, Input, Select};
+use std::path::{Path, PathBuf};
+
+pub fn run(command: &crate::cli::Commands) -> Result<()> {
+    if let crate::cli::Commands::RenameNamespace {
+        old,
+        new,
+        path,
+        dry_run,
+    } = command
+    {
+        let theme = &ColorfulTheme::default();
+
+        let root_dir = match path {
+            Some(p) => PathBuf::from(p),
+            None => std::env::current_dir()?,
+        };
+
+        // verify it's a datapack directory
+        if !root_dir.join("pack.mcmeta").exists() {
+            anyhow::bail!("Not a datapack directory (pack.mcmeta not found)");
+        }
+
+        let namespaces = pack::namespace_dirs(&root_dir)?;
+
+        // prompt for the namespace to rename if not provided
+        let old = match old {
+            Some(old) => old.clone(),
+            None => {
+                let custom: Vec<&String> =
+                    namespaces.iter().filter(|ns| *ns != "minecraft").collect();
+                if custom.is_empty() {
+                    anyhow::bail!("No namespaces found to rename");
+                }
+                let selection = Select::with_theme(theme)
+                    .with_prompt("Select namespace to rename")
+                    .items(&custom)
+                    .interact()?;
+                custom[selection].clone()
+            }
+        };
+
+        if !namespaces.contains(&old) {
+            anyhow::bail!("Namespace '{}' not found in data/", old);
+        }
+
+        let new = match new {
+            Some(new) => new.clone(),
+            None => Input::with_theme(theme)
+                .with_prompt("Enter new namespace name")
+                .interact_text()
+                .context("Failed to get new namespace name")?,
+        };
+
+        if let Some(c) = resource_location::invalid_namespace_char(&new) {
+            anyhow::bail!("Illegal character '{}' in namespace '{}'", c, new);
+        }
+        if new == old {
+            anyhow::bail!("The new namespace is the same as the old one");
+        }
+        if namespaces.contains(&new) {
+            anyhow::bail!("Namespace '{}' already exists", new);
+        }
+
+        let changes = plan_rename(&root_dir, &old, &new)?;
+
+        let moved = changes.iter().filter(|c| c.is_move()).count();
+        let updated = changes.iter().filter(|c| c.content.is_some()).count();
+
+        if *dry_run {
+            println!(
+                "\n{} Renaming namespace '{}' to '{}' would move {} file(s) and update {} file(s):\n",
+                style("🔍").cyan(),
+                style(&old).cyan(),
+                style(&new).cyan(),
+                moved,
+                updated
+            );
+            rewrite::print_changes(&changes);
+            println!();
+            return Ok(());
+        }
+
+        rewrite::apply_changes(&root_dir, &changes)?;
+
+        // keep the project's default namespace pointing at the renamed one
+        let mut config = ProjectConfig::load(&root_dir)?;
+        if config.namespace.as_deref() == Some(old.as_str()) {
+            config.namespace = Some(new.clone());
+            config.save(&root_dir)?;
+        }
+
+        println!(
+            "\n{} Renamed namespace '{}' to '{}' ({} file(s) moved, {} file(s) updated)",
+            style("✓").green(),
+            style(&old).cyan(),
+            style(&new).cyan(),
+            moved,
+            updated
+        );
+    }
+
+    Ok(())
+}
+
+// plan the rename over every file in the pack folder, leaving version control folders alone
+fn plan_rename(root_dir: &Path, old: &str, new: &str) -> Result<Vec<rewrite::FileChange>> {
+    let mut pack = Pack::open(&PackSource::Directory(root_dir.to_path_buf()))?;
+    pack.retain_files(|path| !pack::is_vcs_path(path));
+
+    rewrite::plan_namespace_rename(&mut pack, old, new)
+}
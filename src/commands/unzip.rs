@@ -0,0 +1,229 @@
+use crate::pack::{self, PackSource};
+use anyhow::{Context, Result};
+use console::style;
+use dialoguer::{theme::ColorfulTheme, Confirm, Input};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+
+pub fn run(command: &crate::cli::Commands) -> Result<()> {
+    if let crate::cli::Commands::Unzip {
+        path,
+        name,
+        output_dir,
+        force,
+    } = command
+    {
+        let theme = ColorfulTheme::default();
+
+        // prompt for the archive if not provided
+        let input_path = match path {
+            Some(p) => p.clone(),
+            None => Input::with_theme(&theme)
+                .with_prompt("Enter path to datapack zip file")
+                .interact_text()
+                .context("Failed to get zip file path")?,
+        };
+
+        let zip_path = match pack::resolve_source(Some(&input_path))? {
+            PackSource::Zip(zip_path) => zip_path,
+            PackSource::Directory(_) => {
+                anyhow::bail!("'{}' is a datapack folder, not a zip file", input_path)
+            }
+        };
+
+        let file = File::open(&zip_path)
+            .with_context(|| format!("Failed to open zip file: {}", zip_path.display()))?;
+        let mut archive = ZipArchive::new(file)
+            .with_context(|| format!("Failed to read zip archive: {}", zip_path.display()))?;
+
+        // packs zipped with a top-level folder are extracted without it
+        let prefix = pack::zip_root_prefix(&archive)?;
+
+        // name the folder after the top-level folder if there is one, otherwise the archive
+        let folder_name = match name {
+            Some(name) => {
+                validate_folder_name(name)?;
+                name.clone()
+            }
+            None => match prefix.trim_end_matches('/').rsplit('/').next() {
+                Some(folder) if !folder.is_empty() => folder.to_string(),
+                _ => zip_path
+                    .file_stem()
+                    .context("Invalid zip file path")?
+                    .to_string_lossy()
+                    .to_string(),
+            },
+        };
+
+        let base_dir = match output_dir {
+            Some(dir) => PathBuf::from(dir),
+            None => std::env::current_dir()?,
+        };
+        let directory = base_dir.join(&folder_name);
+
+        if directory.exists() && !force {
+            let confirm = Confirm::with_theme(&theme)
+                .with_prompt(format!("Folder {} already exists. Overwrite?", folder_name))
+                .default(false)
+                .interact()?;
+
+            if !confirm {
+                println!(
+                    "{} {}",
+                    style("✗").red(),
+                    style("Operation cancelled").bold()
+                );
+                return Ok(());
+            }
+        }
+
+        if directory.exists() {
+            fs::remove_dir_all(&directory).with_context(|| {
+                format!(
+                    "Failed to remove existing directory: {}",
+                    directory.display()
+                )
+            })?;
+        }
+
+        fs::create_dir_all(&directory).context("Failed to create datapack directory")?;
+
+        let extracted = extract_archive(&mut archive, &prefix, &directory)?;
+
+        println!(
+            "\n{} {} '{}' ({} files)",
+            style("✨").cyan(),
+            style("Successfully extracted datapack").green().bold(),
+            style(&folder_name).cyan().bold(),
+            extracted
+        );
+        println!(
+            "📂 {}",
+            style(directory.display().to_string()).blue().underlined()
+        );
+    }
+
+    Ok(())
+}
+
+// the folder is removed when overwriting, so the name must stay a single path component
+fn validate_folder_name(name: &str) -> Result<()> {
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+        anyhow::bail!(
+            "Invalid folder name '{}'. It must be a single folder name without path separators",
+            name
+        );
+    }
+    Ok(())
+}
+
+// extract every entry under `prefix` into `directory`, returning the number of files written
+fn extract_archive(
+    archive: &mut ZipArchive<File>,
+    prefix: &str,
+    directory: &Path,
+) -> Result<usize> {
+    let prefix_path = Path::new(prefix);
+    let mut extracted = 0;
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+
+        // reject entries that would escape the output folder (zip-slip)
+        let Some(entry_path) = entry.enclosed_name() else {
+            anyhow::bail!(
+                "Refusing to extract unsafe path from archive: {}",
+                entry.name()
+            );
+        };
+
+        // skip anything outside the pack folder, such as __MACOSX metadata
+        let Ok(relative) = entry_path.strip_prefix(prefix_path) else {
+            continue;
+        };
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+
+        let target = directory.join(relative);
+        if entry.is_dir() {
+            fs::create_dir_all(&target)
+                .with_context(|| format!("Failed to create {}", target.display()))?;
+            continue;
+        }
+
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let mut output = File::create(&target)
+            .with_context(|| format!("Failed to create {}", target.display()))?;
+        io::copy(&mut entry, &mut output)
+            .with_context(|| format!("Failed to extract {}", entry.name()))?;
+        extracted += 1;
+    }
+
+    Ok(extracted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    fn archive_with(entries: &[(&str, &str)]) -> ZipArchive<File> {
+        let mut writer = ZipWriter::new(tempfile::tempfile().unwrap());
+        for (name, content) in entries {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        ZipArchive::new(writer.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn extracts_entries_below_the_prefix() {
+        let output = tempfile::tempdir().unwrap();
+        let mut archive = archive_with(&[
+            ("pack/pack.mcmeta", "{}"),
+            ("pack/data/ns/function/a.mcfunction", "say hi"),
+            ("__MACOSX/pack/._pack.mcmeta", ""),
+        ]);
+
+        let extracted = extract_archive(&mut archive, "pack/", output.path()).unwrap();
+
+        assert_eq!(extracted, 2);
+        assert!(output.path().join("pack.mcmeta").is_file());
+        assert!(output
+            .path()
+            .join("data/ns/function/a.mcfunction")
+            .is_file());
+        assert!(!output.path().join("__MACOSX").exists());
+    }
+
+    #[test]
+    fn rejects_entries_escaping_the_output_folder() {
+        let root = tempfile::tempdir().unwrap();
+        let output = root.path().join("pack");
+        fs::create_dir(&output).unwrap();
+        let mut archive = archive_with(&[("pack.mcmeta", "{}"), ("../evil.txt", "gotcha")]);
+
+        let err = extract_archive(&mut archive, "", &output).unwrap_err();
+
+        assert!(err.to_string().contains("unsafe path"));
+        assert!(!root.path().join("evil.txt").exists());
+    }
+
+    #[test]
+    fn rejects_folder_names_with_separators() {
+        assert!(validate_folder_name("my_pack").is_ok());
+        for name in ["", ".", "..", "../pack", "a/b", "a\\b"] {
+            assert!(validate_folder_name(name).is_err(), "{name}");
+        }
+    }
+}
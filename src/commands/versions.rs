@@ -0,0 +1,52 @@
+use crate::cli::VersionsAction;
+use crate::pack_formats;
+use anyhow::Result;
+use console::style;
+use std::path::Path;
+
+pub fn run(command: &crate::cli::Commands) -> Result<()> {
+    if let crate::cli::Commands::Versions { action } = command {
+        match action {
+            VersionsAction::List { path } => {
+                if let Some(path) = path {
+                    pack_formats::set_project_dir(Some(Path::new(path)));
+                }
+                let table = pack_formats::version_table();
+
+                if !table.overrides.is_empty() {
+                    println!("\n📂 {}", style("Overrides:").blue().bold());
+                    for path in &table.overrides {
+                        println!(
+                            "  {} {}",
+                            style("↪").dim(),
+                            style(path.display().to_string()).underlined()
+                        );
+                    }
+                }
+
+                println!("\n📝 {}", style("Known pack formats:").yellow().bold());
+                // the table is sorted by format, which is also the order versions were released in
+                for (index, version) in table.versions.iter().enumerate() {
+                    let scheme = if pack_formats::is_legacy_format(version.format) {
+                        style(" (pack_format)").dim().to_string()
+                    } else {
+                        String::new()
+                    };
+                    println!(
+                        "  {} {:>2}. {:<8} {}{}",
+                        style("↪").dim(),
+                        index + 1,
+                        style(pack_formats::format_to_string(version.format))
+                            .green()
+                            .bold(),
+                        style(format!("Minecraft {}", version.label)).yellow(),
+                        scheme
+                    );
+                }
+                println!();
+            }
+        }
+    }
+
+    Ok(())
+}
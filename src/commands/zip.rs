@@ -1,13 +1,27 @@
+use crate::commands::check;
+use crate::config::ProjectConfig;
+use crate::pack::{Pack, PackSource};
 use crate::pack_formats;
+use crate::pack_ignore::PackIgnore;
+use crate::validation::{self, Severity};
 use anyhow::{Context, Result};
 use console::style;
 use dialoguer::{theme::ColorfulTheme, Confirm};
 use indicatif::{ProgressBar, ProgressStyle};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
 use std::fs::{self, File};
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
-use zip::{write::FileOptions, ZipWriter};
+use std::sync::mpsc;
+use std::time::Duration;
+use walkdir::WalkDir;
+use zip::{write::FileOptions, DateTime, ZipArchive, ZipWriter};
+
+// how long the pack has to be quiet before a watch rebuild starts
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
 
 pub fn run(command: &crate::cli::Commands) -> Result<()> {
     if let crate::cli::Commands::Zip {
@@ -16,6 +30,10 @@ pub fn run(command: &crate::cli::Commands) -> Result<()> {
         name,
         output_dir,
         input_dir,
+        include,
+        exclude,
+        reproducible,
+        watch,
     } = command
     {
         let datapack_path = match (path, input_dir) {
@@ -32,6 +50,10 @@ pub fn run(command: &crate::cli::Commands) -> Result<()> {
         if !mcmeta_path.exists() {
             anyhow::bail!("Not a datapack directory (pack.mcmeta not found)");
         }
+        let datapack_path = datapack_path
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {}", datapack_path.display()))?;
+        pack_formats::set_project_dir(Some(&datapack_path));
 
         // read pack.mcmeta to get format version and create zip name
         let mcmeta = fs::read_to_string(&mcmeta_path)
@@ -43,25 +65,17 @@ pub fn run(command: &crate::cli::Commands) -> Result<()> {
             .get("pack")
             .context("Invalid pack.mcmeta: missing 'pack' object")?;
 
-        let max_format: Option<[u32; 2]> = pack
-            .get("max_format")
-            .and_then(|v| v.as_array())
-            .and_then(|arr| {
-                if arr.len() != 2 {
-                    return None;
-                }
-                Some([arr[0].as_u64()? as u32, arr[1].as_u64()? as u32])
-            });
+        let max_format = pack_formats::format_range_from_pack(pack).map(|range| range.max);
 
         let datapack_name = datapack_path
             .file_name()
             .context("Invalid datapack path")?
             .to_string_lossy();
 
-        let version_suffix = max_format
-            .and_then(pack_formats::version_for_format)
-            .map(|v| format!("_{}", v.label.replace(" - ", "_").replace(' ', "_")))
-            .unwrap_or_default();
+        let config = ProjectConfig::load(&datapack_path)?;
+
+        let version = max_format.and_then(pack_formats::version_for_format);
+        let version_suffix = version.map(version_suffix).unwrap_or_default();
 
         let zip_name = if let Some(custom_name) = name {
             if !custom_name.ends_with(".zip") {
@@ -71,21 +85,46 @@ pub fn run(command: &crate::cli::Commands) -> Result<()> {
                 );
             }
             custom_name.to_string()
+        } else if let Some(config_name) = config.zip_name(
+            &datapack_name,
+            &version.map(version_slug).unwrap_or_default(),
+            &max_format
+                .map(pack_formats::format_to_string)
+                .unwrap_or_default(),
+        ) {
+            config_name
         } else {
             format!("{}{}.zip", datapack_name, version_suffix)
         };
 
-        // determine output directory
+        // determine output directory, never defaulting to the datapack folder itself
         let output_dir = if let Some(dir) = output_dir {
             PathBuf::from(dir)
+        } else if let Some(dir) = config.output_dir(&datapack_path) {
+            fs::create_dir_all(&dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+            dir
         } else if path.is_some() {
             std::env::current_dir()?
         } else {
-            datapack_path.clone()
+            datapack_path
+                .parent()
+                .context("Datapack folder has no parent directory")?
+                .to_path_buf()
         };
+        let output_dir = output_dir
+            .canonicalize()
+            .with_context(|| format!("Output directory not found: {}", output_dir.display()))?;
 
         let zip_path = output_dir.join(&zip_name);
 
+        if zip_path.starts_with(&datapack_path) {
+            println!(
+                "{} The archive is being written inside the datapack folder and will be excluded from it",
+                style("⚠️").yellow()
+            );
+        }
+
         // check if file exists and prompt for overwrite
         if zip_path.exists() && !force {
             let confirm = Confirm::with_theme(&ColorfulTheme::default())
@@ -106,77 +145,422 @@ pub fn run(command: &crate::cli::Commands) -> Result<()> {
             }
         }
 
-        // count total files to process
-        let total_files = count_files(&datapack_path)?;
-        let pb = ProgressBar::new(total_files);
-        pb.set_style(
-            ProgressStyle::default_bar()
-                .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} files")?
-                .progress_chars("#>-"),
-        );
-        pb.set_message("Creating zip archive...");
+        // files excluded by default, by .mcpackignore or by --exclude/--include
+        let ignore = PackIgnore::load(
+            &datapack_path,
+            include.as_deref().unwrap_or_default(),
+            exclude.as_deref().unwrap_or_default(),
+        )?;
+
+        let (files, skipped_archives) = collect_files(&datapack_path, &ignore, &zip_path)?;
+        for archive in &skipped_archives {
+            println!(
+                "{} Skipping archive inside the datapack folder: {}",
+                style("⚠️").yellow(),
+                style(archive.display()).dim()
+            );
+        }
+
+        let options = archive_options(*reproducible)?;
+
+        build_archive(&datapack_path, &zip_path, &files, options, None)?;
+        print_archive_created(&zip_path, *reproducible)?;
+
+        if *watch {
+            watch_pack(&datapack_path, &zip_path, &ignore, options, *reproducible)?;
+        }
+    }
 
-        // create the zip file
-        let zip_file = File::create(&zip_path)
-            .with_context(|| format!("Failed to create zip file: {}", zip_path.display()))?;
-        let mut zip = ZipWriter::new(zip_file);
-        let options = FileOptions::default()
-            .compression_method(zip::CompressionMethod::Deflated)
-            .unix_permissions(0o755);
+    Ok(())
+}
 
-        // add files to zip with progress
-        add_directory_to_zip(&mut zip, &datapack_path, &datapack_path, options, &pb)?;
+// version label usable in file names, e.g. "26.1_26.1.2"
+pub fn version_slug(version: &pack_formats::SupportedVersion) -> String {
+    version.label.replace(" - ", "_").replace(' ', "_")
+}
 
-        zip.finish()?;
-        pb.finish_with_message("Archive created successfully!");
+// suffix added to default archive names for a version, e.g. "_26.1_26.1.2"
+pub fn version_suffix(version: &pack_formats::SupportedVersion) -> String {
+    format!("_{}", version_slug(version))
+}
 
+// zip a datapack folder with the default ignore rules, for commands that need an archive
+pub fn zip_pack(datapack_path: &Path, zip_path: &Path) -> Result<()> {
+    let ignore = PackIgnore::load(datapack_path, &[], &[])?;
+    let (files, _) = collect_files(datapack_path, &ignore, zip_path)?;
+    build_archive(
+        datapack_path,
+        zip_path,
+        &files,
+        archive_options(false)?,
+        None,
+    )
+}
+
+fn archive_options(reproducible: bool) -> Result<FileOptions<'static, ()>> {
+    let mut options = FileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .unix_permissions(0o644);
+    if reproducible {
+        options = options.last_modified_time(reproducible_timestamp()?);
+    }
+    Ok(options)
+}
+
+fn print_archive_created(zip_path: &Path, reproducible: bool) -> Result<()> {
+    println!(
+        "\n{} Created datapack archive: {}",
+        style("✓").green(),
+        style(zip_path.file_name().unwrap().to_string_lossy()).cyan()
+    );
+
+    if reproducible {
         println!(
-            "\n{} Created datapack archive: {}",
-            style("✓").green(),
-            style(zip_path.file_name().unwrap().to_string_lossy()).cyan()
+            "{} SHA-256: {}",
+            style("🔒").cyan(),
+            style(sha256_file(zip_path)?).dim()
         );
     }
 
     Ok(())
 }
 
-fn count_files(dir_path: &Path) -> Result<u64> {
-    let mut count = 0;
-    for entry in fs::read_dir(dir_path)? {
+// entries of an earlier build that can be copied as-is instead of being recompressed
+struct PreviousArchive {
+    archive: ZipArchive<File>,
+    changed: HashSet<PathBuf>,
+}
+
+// write the archive, reusing unchanged entries from a previous build when given one
+fn build_archive(
+    base_path: &Path,
+    zip_path: &Path,
+    files: &[PathBuf],
+    options: FileOptions<'_, ()>,
+    previous: Option<PreviousArchive>,
+) -> Result<()> {
+    let pb = ProgressBar::new(files.len() as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} files")?
+            .progress_chars("#>-"),
+    );
+    pb.set_message("Creating zip archive...");
+
+    // create the zip file
+    let zip_file = File::create(zip_path)
+        .with_context(|| format!("Failed to create zip file: {}", zip_path.display()))?;
+    let mut zip = ZipWriter::new(zip_file);
+
+    // add files to zip with progress
+    add_files_to_zip(&mut zip, base_path, files, options, previous, &pb)?;
+
+    zip.finish()?;
+    pb.finish_with_message("Archive created successfully!");
+
+    Ok(())
+}
+
+// rebuild the archive whenever files in the pack change, until interrupted
+fn watch_pack(
+    datapack_path: &Path,
+    zip_path: &Path,
+    ignore: &PackIgnore,
+    options: FileOptions<'_, ()>,
+    reproducible: bool,
+) -> Result<()> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).context("Failed to start file watcher")?;
+    watcher
+        .watch(datapack_path, RecursiveMode::Recursive)
+        .with_context(|| format!("Failed to watch {}", datapack_path.display()))?;
+
+    println!(
+        "\n{} Watching {} for changes (press Ctrl+C to stop)",
+        style("👀").cyan(),
+        style(datapack_path.display()).cyan()
+    );
+
+    // rebuilds are written next to the archive and moved into place once complete
+    let temp_path = zip_path.with_extension("zip.tmp");
+
+    loop {
+        // wait for a change, then keep collecting events until writes settle down
+        let mut changed = HashSet::new();
+        let event = rx.recv().context("File watcher stopped unexpectedly")?;
+        collect_changes(
+            event,
+            datapack_path,
+            &[zip_path, &temp_path],
+            ignore,
+            &mut changed,
+        );
+        while let Ok(event) = rx.recv_timeout(WATCH_DEBOUNCE) {
+            collect_changes(
+                event,
+                datapack_path,
+                &[zip_path, &temp_path],
+                ignore,
+                &mut changed,
+            );
+        }
+
+        if changed.is_empty() {
+            continue;
+        }
+
+        println!(
+            "\n{} {} file(s) changed, rebuilding archive...",
+            style("🔄").cyan(),
+            changed.len()
+        );
+
+        // report failures and keep watching rather than exiting
+        if let Err(err) = rebuild_archive(
+            datapack_path,
+            zip_path,
+            &temp_path,
+            ignore,
+            options,
+            changed,
+            reproducible,
+        ) {
+            eprintln!("{} {:#}", style("error:").red().bold(), err);
+        }
+    }
+}
+
+fn collect_changes(
+    event: notify::Result<Event>,
+    datapack_path: &Path,
+    outputs: &[&Path],
+    ignore: &PackIgnore,
+    changed: &mut HashSet<PathBuf>,
+) {
+    let Ok(event) = event else {
+        return;
+    };
+    if matches!(event.kind, EventKind::Access(_)) {
+        return;
+    }
+
+    for path in event.paths {
+        if outputs.contains(&path.as_path()) {
+            continue;
+        }
+        let Ok(relative) = path.strip_prefix(datapack_path) else {
+            continue;
+        };
+        if !ignore.is_ignored(relative, path.is_dir()) {
+            changed.insert(relative.to_path_buf());
+        }
+    }
+}
+
+fn rebuild_archive(
+    datapack_path: &Path,
+    zip_path: &Path,
+    temp_path: &Path,
+    ignore: &PackIgnore,
+    options: FileOptions<'_, ()>,
+    changed: HashSet<PathBuf>,
+    reproducible: bool,
+) -> Result<()> {
+    // report problems in the files the game loads from the archive (pack.mcmeta and the
+    // data/ folders of the pack and its overlays), without stopping the rebuild
+    let mut pack = Pack::open(&PackSource::Directory(datapack_path.to_path_buf()))?;
+    pack.retain_files(|path| {
+        let loaded = path == "pack.mcmeta"
+            || path.starts_with("data/")
+            || path
+                .split_once('/')
+                .is_some_and(|(_, rest)| rest.starts_with("data/"));
+        loaded && !ignore.is_ignored(Path::new(path), false)
+    });
+    let diagnostics = validation::validate_pack(&mut pack)?;
+    if check::count_severity(&diagnostics, Severity::Error) > 0 {
+        check::display_diagnostics(&diagnostics);
+    }
+
+    let (files, _) = collect_files(datapack_path, ignore, zip_path)?;
+    let previous = File::open(zip_path)
+        .ok()
+        .and_then(|file| ZipArchive::new(file).ok())
+        .map(|archive| PreviousArchive { archive, changed });
+
+    build_archive(datapack_path, temp_path, &files, options, previous)?;
+    fs::rename(temp_path, zip_path)
+        .with_context(|| format!("Failed to replace {}", zip_path.display()))?;
+
+    print_archive_created(zip_path, reproducible)
+}
+
+// every file to archive, relative to the pack root, along with any zip archives found in
+// the pack that were skipped
+pub fn collect_files(
+    base_path: &Path,
+    ignore: &PackIgnore,
+    output_path: &Path,
+) -> Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+    let mut files = Vec::new();
+    let mut skipped_archives = Vec::new();
+
+    let walker = WalkDir::new(base_path)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| match entry.path().strip_prefix(base_path) {
+            Ok(relative) if relative.as_os_str().is_empty() => true,
+            Ok(relative) => !ignore.is_ignored(relative, entry.file_type().is_dir()),
+            Err(_) => false,
+        });
+
+    for entry in walker {
         let entry = entry?;
-        let path = entry.path();
-        if path.is_dir() {
-            count += count_files(&path)?;
-        } else {
-            count += 1;
+        if !entry.file_type().is_file() || entry.path() == output_path {
+            continue;
+        }
+
+        let relative = entry.path().strip_prefix(base_path)?.to_path_buf();
+
+        // archives left in the pack (e.g. from earlier runs) are never packed into a new one
+        if relative.extension().is_some_and(|ext| ext == "zip") {
+            skipped_archives.push(relative);
+            continue;
         }
+
+        files.push(relative);
     }
-    Ok(count)
+
+    Ok((files, skipped_archives))
 }
 
-fn add_directory_to_zip<'a>(
+fn add_files_to_zip<'a>(
     zip: &mut ZipWriter<File>,
     base_path: &Path,
-    dir_path: &Path,
+    files: &[PathBuf],
     options: FileOptions<'a, ()>,
+    mut previous: Option<PreviousArchive>,
     progress: &ProgressBar,
 ) -> Result<()> {
-    for entry in fs::read_dir(dir_path)? {
-        let entry = entry?;
-        let path = entry.path();
-        let relative_path = path.strip_prefix(base_path)?;
+    for relative_path in files {
+        let name = relative_path.to_string_lossy().replace('\\', "/");
 
-        if path.is_dir() {
-            add_directory_to_zip(zip, base_path, &path, options, progress)?;
-        } else {
-            zip.start_file(relative_path.to_string_lossy().replace('\\', "/"), options)?;
-            let mut file = File::open(&path)?;
-            let mut buffer = Vec::new();
-            file.read_to_end(&mut buffer)?;
-            zip.write_all(&buffer)?;
-            progress.inc(1);
+        // copy unchanged entries straight from the previous build
+        if let Some(previous) = previous.as_mut() {
+            if !previous.changed.contains(relative_path) {
+                if let Ok(entry) = previous.archive.by_name(&name) {
+                    zip.raw_copy_file(entry)?;
+                    progress.inc(1);
+                    continue;
+                }
+            }
         }
+
+        zip.start_file(name, options)?;
+        let mut file = File::open(base_path.join(relative_path))?;
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer)?;
+        zip.write_all(&buffer)?;
+        progress.inc(1);
     }
 
     Ok(())
 }
+
+// fixed modification time for reproducible archives, taken from SOURCE_DATE_EPOCH when set
+fn reproducible_timestamp() -> Result<DateTime> {
+    let Ok(epoch) = std::env::var("SOURCE_DATE_EPOCH") else {
+        return Ok(DateTime::default());
+    };
+    let seconds: i64 = epoch
+        .trim()
+        .parse()
+        .with_context(|| format!("Invalid SOURCE_DATE_EPOCH: '{}'", epoch))?;
+
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86400));
+    let time_of_day = seconds.rem_euclid(86400);
+
+    // zip timestamps can't represent anything before 1980
+    if year < 1980 {
+        return Ok(DateTime::default());
+    }
+
+    DateTime::from_date_and_time(
+        u16::try_from(year).unwrap_or(u16::MAX),
+        month,
+        day,
+        (time_of_day / 3600) as u8,
+        (time_of_day % 3600 / 60) as u8,
+        (time_of_day % 60) as u8,
+    )
+    .map_err(|_| anyhow::anyhow!("SOURCE_DATE_EPOCH is outside the range zip archives support"))
+}
+
+// convert days since the unix epoch into a (year, month, day) date
+fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month as u8, day as u8)
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = File::open(path)
+        .with_context(|| format!("Failed to open {} for hashing", path.display()))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_days_to_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+        assert_eq!(civil_from_days(20_088), (2024, 12, 31));
+    }
+
+    // the only test touching SOURCE_DATE_EPOCH, so parallel tests don't race on it
+    #[test]
+    fn reads_timestamp_from_source_date_epoch() {
+        std::env::remove_var("SOURCE_DATE_EPOCH");
+        assert_eq!(reproducible_timestamp().unwrap(), DateTime::default());
+
+        // 2024-02-29 13:45:30 UTC
+        std::env::set_var("SOURCE_DATE_EPOCH", "1709214330");
+        let timestamp = reproducible_timestamp().unwrap();
+        assert_eq!(
+            (timestamp.year(), timestamp.month(), timestamp.day()),
+            (2024, 2, 29)
+        );
+        assert_eq!(
+            (timestamp.hour(), timestamp.minute(), timestamp.second()),
+            (13, 45, 30)
+        );
+
+        // dates before 1980 fall back to the zip epoch
+        std::env::set_var("SOURCE_DATE_EPOCH", "0");
+        assert_eq!(reproducible_timestamp().unwrap(), DateTime::default());
+
+        std::env::set_var("SOURCE_DATE_EPOCH", "yesterday");
+        assert!(reproducible_timestamp().is_err());
+
+        std::env::remove_var("SOURCE_DATE_EPOCH");
+    }
+}
@@ -0,0 +1,305 @@
+use crate::elements;
+use crate::pack_formats::{self, FormatRange};
+use crate::resource_location;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// project settings file in the pack root, read as defaults by commands
+pub const PROJECT_FILE: &str = "mcpack.toml";
+
+// settings stored in mcpack.toml; every field is optional and flags always take priority
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectConfig {
+    // namespace used by `add` when none is given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    // directory `zip` writes archives to, relative to the pack root
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_dir: Option<String>,
+    // archive name pattern using {name}, {version} and {format}
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zip_name: Option<String>,
+    // extra gitignore-style patterns left out of archives
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<String>,
+    // pack format range the project targets
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<FormatConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FormatConfig {
+    pub min: String,
+    pub max: String,
+}
+
+impl FormatConfig {
+    // the range, naming the file it came from in errors
+    pub fn range(&self, file: &str) -> Result<FormatRange> {
+        let parse = |s: &str| {
+            pack_formats::parse_format_string(s).with_context(|| {
+                format!(
+                    "Invalid format '{}' in {}. Must be a decimal format (e.g. 101.1)",
+                    s, file
+                )
+            })
+        };
+        Ok(FormatRange {
+            min: parse(&self.min)?,
+            max: parse(&self.max)?,
+        })
+    }
+}
+
+impl ProjectConfig {
+    // read mcpack.toml from a pack root, falling back to empty settings when there is none
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(PROJECT_FILE);
+        if !path.exists() {
+            return Ok(ProjectConfig::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let content = toml::to_string_pretty(self).context("Failed to serialize mcpack.toml")?;
+        fs::write(root.join(PROJECT_FILE), content).context("Failed to write mcpack.toml")
+    }
+
+    // the format range the project targets
+    pub fn format_range(&self) -> Result<Option<FormatRange>> {
+        self.format
+            .as_ref()
+            .map(|format| format.range(PROJECT_FILE))
+            .transpose()
+    }
+
+    // output directory resolved against the pack root
+    pub fn output_dir(&self, root: &Path) -> Option<PathBuf> {
+        self.output_dir.as_ref().map(|dir| root.join(dir))
+    }
+
+    // fill in the zip_name pattern, adding the .zip extension if it's missing
+    pub fn zip_name(&self, name: &str, version: &str, format: &str) -> Option<String> {
+        let pattern = self.zip_name.as_ref()?;
+        let stem = pattern
+            .replace("{name}", name)
+            .replace("{version}", version)
+            .replace("{format}", format);
+        // unknown versions leave an empty placeholder, so drop any separator left dangling
+        let stem = stem
+            .trim_end_matches(".zip")
+            .trim_end_matches(['_', '-', '.']);
+        Some(format!("{}.zip", stem))
+    }
+}
+
+// per-user defaults file, stored in the platform config directory under "mcpack"
+pub const USER_CONFIG_FILE: &str = "config.toml";
+
+// keys accepted by `mcpack config`
+pub const USER_CONFIG_KEYS: &[(&str, &str)] = &[
+    ("description", "Default description for new datapacks"),
+    ("icon", "Path to a PNG used as the icon of new datapacks"),
+    ("namespace", "Default custom namespace for new datapacks"),
+    (
+        "starters",
+        "Starter folders selected by default for new datapacks",
+    ),
+    (
+        "format",
+        "Default pack format range for new datapacks (one or two formats)",
+    ),
+    (
+        "minecraft_dir",
+        "Path to the .minecraft folder used by deploy",
+    ),
+];
+
+// defaults shared by every project of one developer
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UserConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starters: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minecraft_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<FormatConfig>,
+}
+
+// directory holding the user's config file and other per-user data
+pub fn user_config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("mcpack"))
+}
+
+pub fn user_config_path() -> Result<PathBuf> {
+    user_config_dir()
+        .map(|dir| dir.join(USER_CONFIG_FILE))
+        .context("Could not determine the user config directory")
+}
+
+impl UserConfig {
+    // read the user config, falling back to empty settings when there is none
+    pub fn load() -> Result<Self> {
+        let Some(dir) = user_config_dir() else {
+            return Ok(UserConfig::default());
+        };
+        let path = dir.join(USER_CONFIG_FILE);
+        if !path.exists() {
+            return Ok(UserConfig::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = user_config_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let content = toml::to_string_pretty(self).context("Failed to serialize user config")?;
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    // the default format range for new datapacks
+    pub fn format_range(&self) -> Result<Option<FormatRange>> {
+        self.format
+            .as_ref()
+            .map(|format| format.range(USER_CONFIG_FILE))
+            .transpose()
+    }
+
+    // current value of a key as shown by `mcpack config get`
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        let value = match key {
+            "description" => self.description.clone(),
+            "icon" => self.icon.clone(),
+            "namespace" => self.namespace.clone(),
+            "starters" => self.starters.as_ref().map(|starters| starters.join(" ")),
+            "format" => self
+                .format
+                .as_ref()
+                .map(|format| format!("{} {}", format.min, format.max)),
+            "minecraft_dir" => self.minecraft_dir.clone(),
+            _ => return Err(unknown_key(key)),
+        };
+        Ok(value)
+    }
+
+    // validate and store a value given on the command line
+    pub fn set(&mut self, key: &str, values: &[String]) -> Result<()> {
+        let joined = values.join(" ");
+        match key {
+            "description" => self.description = Some(joined),
+            "icon" => {
+                let icon = Path::new(joined.trim_matches(|c| c == '"' || c == '\''));
+                if !icon
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("png"))
+                {
+                    anyhow::bail!("Icon must be a PNG file");
+                }
+                let icon = icon
+                    .canonicalize()
+                    .with_context(|| format!("Icon file does not exist: {}", icon.display()))?;
+                self.icon = Some(icon.to_string_lossy().to_string());
+            }
+            "namespace" => {
+                if let Some(c) = resource_location::invalid_namespace_char(&joined) {
+                    anyhow::bail!("Illegal character '{}' in namespace '{}'", c, joined);
+                }
+                self.namespace = Some(joined);
+            }
+            "starters" => {
+                for starter in values {
+                    if !elements::is_valid_element_type(starter) {
+                        anyhow::bail!(
+                            "Invalid starter folder name: '{}'. Valid options are: {}",
+                            starter,
+                            elements::element_types_hint()
+                        );
+                    }
+                }
+                let mut starters = values.to_vec();
+                starters.sort();
+                starters.dedup();
+                self.starters = Some(starters);
+            }
+            "format" => {
+                if values.len() > 2 {
+                    anyhow::bail!("Provide one format, or two to define a min..max range");
+                }
+                let mut formats = values
+                    .iter()
+                    .map(|s| {
+                        pack_formats::parse_format_string(s).with_context(|| {
+                            format!(
+                                "Invalid pack format: '{}'. Must be a decimal format (e.g. 101.1)",
+                                s
+                            )
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                // new datapacks are written with the decimal scheme only
+                if let Some(format) = formats.iter().find(|f| {
+                    !pack_formats::is_supported_format(**f) || pack_formats::is_legacy_format(**f)
+                }) {
+                    anyhow::bail!(
+                        "Pack format '{}' is not supported",
+                        pack_formats::format_to_string(*format)
+                    );
+                }
+                formats.sort();
+                self.format = Some(FormatConfig {
+                    min: pack_formats::format_to_string(formats[0]),
+                    max: pack_formats::format_to_string(formats[formats.len() - 1]),
+                });
+            }
+            "minecraft_dir" => self.minecraft_dir = Some(joined),
+            _ => return Err(unknown_key(key)),
+        }
+        Ok(())
+    }
+
+    pub fn unset(&mut self, key: &str) -> Result<()> {
+        match key {
+            "description" => self.description = None,
+            "icon" => self.icon = None,
+            "namespace" => self.namespace = None,
+            "starters" => self.starters = None,
+            "format" => self.format = None,
+            "minecraft_dir" => self.minecraft_dir = None,
+            _ => return Err(unknown_key(key)),
+        }
+        Ok(())
+    }
+}
+
+fn unknown_key(key: &str) -> anyhow::Error {
+    anyhow::anyhow!(
+        "Unknown config key '{}'. Valid keys are: {}",
+        key,
+        USER_CONFIG_KEYS
+            .iter()
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
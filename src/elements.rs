@@ -1,28 +1,33 @@
-// all elements and their file extensions
-pub const ELEMENT_TYPES: &[(&str, &str)] = &[
-    ("function", ".mcfunction"),
-    ("tag", ".json"),
-    ("advancement", ".json"),
-    ("banner_pattern", ".json"),
-    ("chat_type", ".json"),
-    ("damage_type", ".json"),
-    ("enchantment", ".json"),
-    ("enchantment_provider", ".json"),
-    ("instrument", ".json"),
-    ("item_modifier", ".json"),
-    ("jukebox_song", ".json"),
-    ("loot_table", ".json"),
-    ("painting_variant", ".json"),
-    ("predicate", ".json"),
-    ("recipe", ".json"),
-    ("trim_material", ".json"),
-    ("trim_pattern", ".json"),
-    ("walk_variant", ".json"),
-];
+use crate::registries::{self, REGISTRIES};
+
+// all element types, one per registry folder of text files; tag folders of any registry
+// (e.g. tags/function) are accepted as well. structures are NBT files saved in game, so
+// there's no starter file to write for them
+pub fn element_types() -> Vec<&'static str> {
+    REGISTRIES
+        .iter()
+        .filter(|registry| registry.extension != ".nbt")
+        .map(|registry| registry.name)
+        .collect()
+}
 
 // check if an element type is valid
 pub fn is_valid_element_type(element_type: &str) -> bool {
-    ELEMENT_TYPES.iter().any(|(name, _)| *name == element_type)
+    if element_type.starts_with("tags/") {
+        registries::is_registry(element_type)
+    } else {
+        element_types().contains(&element_type)
+    }
+}
+
+// file extension of an element type
+pub fn element_extension(element_type: &str) -> &'static str {
+    registries::extension_for_registry(element_type)
+}
+
+// valid element types, for error messages
+pub fn element_types_hint() -> String {
+    format!("{} or tags/<registry>", element_types().join(", "))
 }
 
 // all elements and their template files
@@ -115,8 +120,8 @@ pub fn get_sample_content(element_type: &str) -> String {
           "type": ""
         }))
         .unwrap(),
-        "tag" => serde_json::to_string_pretty(&serde_json::json!({
-            "values": ["minecraft:stone"]
+        tag if tag.starts_with("tags/") => serde_json::to_string_pretty(&serde_json::json!({
+            "values": []
         }))
         .unwrap(),
         "trim_material" => serde_json::to_string_pretty(&serde_json::json!({
@@ -1,4 +1,14 @@
 pub mod cli;
 pub mod commands;
+pub mod config;
 pub mod elements;
+pub mod pack;
 pub mod pack_formats;
+pub mod pack_ignore;
+pub mod references;
+pub mod registries;
+pub mod resource_location;
+pub mod rewrite;
+pub mod templates;
+pub mod text_component;
+pub mod validation;
@@ -20,6 +20,18 @@ fn run() -> Result<()> {
         Commands::Info { .. } => commands::info::run(&cli.command)?,
         Commands::Zip { .. } => commands::zip::run(&cli.command)?,
         Commands::Add { .. } => commands::add::run(&cli.command)?,
+        Commands::Remove { .. } => commands::remove::run(&cli.command)?,
+        Commands::Diff { .. } => commands::diff::run(&cli.command)?,
+        Commands::Ls { .. } => commands::ls::run(&cli.command)?,
+        Commands::Check { .. } => commands::check::run(&cli.command)?,
+        Commands::Unzip { .. } => commands::unzip::run(&cli.command)?,
+        Commands::Deploy { .. } => commands::deploy::run(&cli.command)?,
+        Commands::RenameNamespace { .. } => commands::rename_namespace::run(&cli.command)?,
+        Commands::Mv { .. } => commands::mv::run(&cli.command)?,
+        Commands::Migrate { .. } => commands::migrate::run(&cli.command)?,
+        Commands::Overlay { .. } => commands::overlay::run(&cli.command)?,
+        Commands::Config { .. } => commands::config::run(&cli.command)?,
+        Commands::Versions { .. } => commands::versions::run(&cli.command)?,
     }
 
     Ok(())
@@ -0,0 +1,331 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+use zip::ZipArchive;
+
+// where a datapack lives on disk
+#[derive(Debug, Clone)]
+pub enum PackSource {
+    Directory(PathBuf),
+    Zip(PathBuf),
+}
+
+impl PackSource {
+    // the pack folder, or none for a zip archive
+    pub fn directory(&self) -> Option<&Path> {
+        match self {
+            PackSource::Directory(dir) => Some(dir),
+            PackSource::Zip(_) => None,
+        }
+    }
+}
+
+// resolve a user supplied name/path into a datapack folder or zip archive
+pub fn resolve_source(path: Option<&str>) -> Result<PackSource> {
+    let Some(input_path) = path else {
+        let current_dir = std::env::current_dir()?;
+        if !current_dir.join("pack.mcmeta").exists() {
+            anyhow::bail!("Not in a datapack directory (pack.mcmeta not found)");
+        }
+        return Ok(PackSource::Directory(current_dir));
+    };
+
+    let path_obj = Path::new(input_path);
+
+    // check if it's a directory (datapack folder)
+    if path_obj.is_dir() {
+        if !path_obj.join("pack.mcmeta").exists() {
+            anyhow::bail!(
+                "Not a valid datapack directory: pack.mcmeta not found in {}",
+                input_path
+            );
+        }
+        return Ok(PackSource::Directory(path_obj.to_path_buf()));
+    }
+
+    // if it's not a directory, check if it's a zip file
+    if path_obj.exists() && input_path.ends_with(".zip") {
+        return Ok(PackSource::Zip(path_obj.to_path_buf()));
+    }
+
+    // if neither exists, try appending .zip to the path
+    let mut zip_path = String::from(input_path);
+    if !zip_path.ends_with(".zip") {
+        zip_path.push_str(".zip");
+    }
+
+    if Path::new(&zip_path).exists() {
+        Ok(PackSource::Zip(PathBuf::from(zip_path)))
+    } else {
+        anyhow::bail!(
+            "Neither datapack folder '{}' nor zip file '{}' found",
+            input_path,
+            zip_path
+        );
+    }
+}
+
+// namespace folders in the data/ folder of a pack directory, sorted
+pub fn namespace_dirs(root: &Path) -> Result<Vec<String>> {
+    let data_dir = root.join("data");
+    if !data_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut namespaces: Vec<String> = fs::read_dir(&data_dir)?
+        .filter_map(|entry| {
+            entry.ok().and_then(|e| {
+                if e.file_type().ok()?.is_dir() {
+                    Some(e.file_name().to_string_lossy().to_string())
+                } else {
+                    None
+                }
+            })
+        })
+        .collect();
+    namespaces.sort();
+    Ok(namespaces)
+}
+
+// version control folders, which commands that rewrite the pack folder never touch
+pub const VCS_DIRS: &[&str] = &[".git", ".svn", ".hg"];
+
+// whether a relative pack path lies inside a version control folder
+pub fn is_vcs_path(path: &str) -> bool {
+    path.split('/')
+        .next()
+        .is_some_and(|first| VCS_DIRS.contains(&first))
+}
+
+// folder inside a zip archive that holds pack.mcmeta, as a prefix such as "my_pack/"
+// (empty when the pack is at the root of the archive)
+pub fn zip_root_prefix(archive: &ZipArchive<fs::File>) -> Result<String> {
+    // packs are sometimes zipped with a top-level folder, so use the shallowest pack.mcmeta
+    archive
+        .file_names()
+        .filter(|n| *n == "pack.mcmeta" || n.ends_with("/pack.mcmeta"))
+        .min_by_key(|n| n.matches('/').count())
+        .map(|n| n.trim_end_matches("pack.mcmeta").to_string())
+        .context("pack.mcmeta not found in zip archive")
+}
+
+enum PackRoot {
+    Directory(PathBuf),
+    Zip {
+        archive: ZipArchive<fs::File>,
+        prefix: String,
+        indices: HashMap<String, usize>,
+    },
+}
+
+// read access to every file of a datapack, independent of folder or zip storage
+pub struct Pack {
+    pub name: String,
+    files: Vec<String>,
+    root: PackRoot,
+}
+
+impl Pack {
+    pub fn open(source: &PackSource) -> Result<Pack> {
+        match source {
+            PackSource::Directory(dir) => Self::open_directory(dir),
+            PackSource::Zip(zip_path) => Self::open_zip(zip_path),
+        }
+    }
+
+    fn open_directory(dir: &Path) -> Result<Pack> {
+        let name = dir
+            .canonicalize()
+            .unwrap_or_else(|_| dir.to_path_buf())
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
+        let mut files = Vec::new();
+        for entry in WalkDir::new(dir) {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative = entry.path().strip_prefix(dir)?;
+            files.push(relative.to_string_lossy().replace('\\', "/"));
+        }
+        files.sort();
+
+        Ok(Pack {
+            name,
+            files,
+            root: PackRoot::Directory(dir.to_path_buf()),
+        })
+    }
+
+    fn open_zip(zip_path: &Path) -> Result<Pack> {
+        let file = fs::File::open(zip_path)
+            .with_context(|| format!("Failed to open zip file: {}", zip_path.display()))?;
+        let mut archive = ZipArchive::new(file)
+            .with_context(|| format!("Failed to read zip archive: {}", zip_path.display()))?;
+
+        // entries are written back to disk by some commands, so refuse ones that would
+        // escape the target folder (zip-slip)
+        for i in 0..archive.len() {
+            let entry = archive.by_index_raw(i)?;
+            if entry.enclosed_name().is_none() {
+                anyhow::bail!(
+                    "Refusing to read unsafe path from archive: {}",
+                    entry.name()
+                );
+            }
+        }
+
+        let prefix = zip_root_prefix(&archive)?;
+
+        let mut files = Vec::new();
+        let mut indices = HashMap::new();
+        for i in 0..archive.len() {
+            let Some(name) = archive.name_for_index(i) else {
+                continue;
+            };
+            if name.ends_with('/') {
+                continue;
+            }
+            if let Some(relative) = name.strip_prefix(&prefix) {
+                files.push(relative.to_string());
+                indices.insert(relative.to_string(), i);
+            }
+        }
+        files.sort();
+
+        let name = zip_path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
+        Ok(Pack {
+            name,
+            files,
+            root: PackRoot::Zip {
+                archive,
+                prefix,
+                indices,
+            },
+        })
+    }
+
+    // relative paths of every file in the pack, sorted and '/' separated
+    pub fn files(&self) -> &[String] {
+        &self.files
+    }
+
+    // drop files from the pack, e.g. ones that are excluded from archives
+    pub fn retain_files(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        self.files.retain(|path| keep(path));
+    }
+
+    // namespace folders inside data/, sorted
+    pub fn namespaces(&self) -> Vec<String> {
+        let mut namespaces: Vec<String> = self
+            .files
+            .iter()
+            .filter_map(|path| path.strip_prefix("data/")?.split_once('/'))
+            .map(|(namespace, _)| namespace.to_string())
+            .collect();
+        // files are sorted, so each namespace's files are next to each other
+        namespaces.dedup();
+        namespaces
+    }
+
+    pub fn contains(&self, path: &str) -> bool {
+        self.files
+            .binary_search_by(|f| f.as_str().cmp(path))
+            .is_ok()
+    }
+
+    pub fn read(&mut self, path: &str) -> Result<Vec<u8>> {
+        match &mut self.root {
+            PackRoot::Directory(dir) => {
+                fs::read(dir.join(path)).with_context(|| format!("Failed to read {}", path))
+            }
+            PackRoot::Zip {
+                archive,
+                prefix,
+                indices,
+            } => {
+                let index = *indices
+                    .get(path)
+                    .with_context(|| format!("{}{} not found in zip archive", prefix, path))?;
+                let mut file = archive.by_index(index)?;
+                let mut buffer = Vec::new();
+                file.read_to_end(&mut buffer)
+                    .with_context(|| format!("Failed to read {}", path))?;
+                Ok(buffer)
+            }
+        }
+    }
+
+    pub fn read_to_string(&mut self, path: &str) -> Result<String> {
+        String::from_utf8(self.read(path)?).with_context(|| format!("{} is not valid UTF-8", path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    fn zip_with(path: &Path, entries: &[(&str, &str)]) {
+        let mut writer = ZipWriter::new(fs::File::create(path).unwrap());
+        for (name, content) in entries {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn opens_zips_with_a_top_level_folder() {
+        let root = tempfile::tempdir().unwrap();
+        let zip_path = root.path().join("pack.zip");
+        zip_with(
+            &zip_path,
+            &[
+                ("pack/pack.mcmeta", "{}"),
+                ("pack/data/ns/function/a.mcfunction", "say hi"),
+            ],
+        );
+
+        let mut pack = Pack::open_zip(&zip_path).unwrap();
+
+        assert_eq!(
+            pack.files(),
+            ["data/ns/function/a.mcfunction", "pack.mcmeta"]
+        );
+        assert_eq!(
+            pack.read_to_string("data/ns/function/a.mcfunction")
+                .unwrap(),
+            "say hi"
+        );
+    }
+
+    #[test]
+    fn rejects_entries_escaping_the_output_folder() {
+        let root = tempfile::tempdir().unwrap();
+        let zip_path = root.path().join("evil.zip");
+        zip_with(
+            &zip_path,
+            &[("pack.mcmeta", "{}"), ("../../escaped.txt", "gotcha")],
+        );
+
+        let err = Pack::open_zip(&zip_path).err().unwrap();
+
+        assert!(err.to_string().contains("unsafe path"));
+    }
+}
@@ -1,35 +1,226 @@
+use crate::config;
+use crate::templates::PROJECT_DATA_DIR;
+use anyhow::{Context, Result};
+use console::style;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+// version table file bundled with mcpack, which a user or project versions.json can extend
+pub const VERSIONS_FILE: &str = "versions.json";
+
+const BUNDLED_VERSIONS: &str = include_str!("../assets/versions.json");
+
+// first format of the decimal min_format/max_format scheme; packs for earlier versions
+// declare an integer pack_format (and optionally supported_formats) instead
+pub const FIRST_DECIMAL_FORMAT: [u32; 2] = [82, 0];
+
+#[derive(Debug, Clone)]
 pub struct SupportedVersion {
     pub format: [u32; 2],
-    pub label: &'static str,
+    pub label: String,
+    // registry folders this version renamed, e.g. "functions" to "function"
+    pub renames: BTreeMap<String, String>,
+}
+
+// known versions in release order, and the override files that were merged into them
+pub struct VersionTable {
+    pub versions: Vec<SupportedVersion>,
+    pub overrides: Vec<PathBuf>,
+}
+
+#[derive(Deserialize)]
+struct VersionsFile {
+    versions: Vec<VersionEntry>,
+}
+
+#[derive(Deserialize)]
+struct VersionEntry {
+    format: String,
+    label: String,
+    #[serde(default)]
+    renames: BTreeMap<String, String>,
+}
+
+static VERSION_TABLE: OnceLock<VersionTable> = OnceLock::new();
+static PROJECT_DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+// the pack folder a command works on, whose .mcpack/versions.json is merged into the table
+// (none for zip archives); has to be set before the table is first used, and commands that
+// don't set it read the current directory's
+pub fn set_project_dir(dir: Option<&Path>) {
+    let _ = PROJECT_DIR.set(dir.map(Path::to_path_buf));
+}
+
+// the bundled table merged with the user's versions.json, then the one in the project's
+// .mcpack folder; later files replace the label of formats already listed and add to their
+// renames
+pub fn version_table() -> &'static VersionTable {
+    VERSION_TABLE.get_or_init(|| {
+        let mut table = VersionTable {
+            versions: parse_versions(BUNDLED_VERSIONS).expect("bundled versions.json is valid"),
+            overrides: Vec::new(),
+        };
+
+        let project_dir = match PROJECT_DIR.get() {
+            Some(dir) => dir.clone(),
+            None => std::env::current_dir().ok(),
+        };
+        let override_paths = [
+            config::user_config_dir().map(|dir| dir.join(VERSIONS_FILE)),
+            project_dir.map(|dir| dir.join(PROJECT_DATA_DIR).join(VERSIONS_FILE)),
+        ];
+        for path in override_paths.into_iter().flatten() {
+            if !path.is_file() {
+                continue;
+            }
+            match load_versions_file(&path) {
+                Ok(versions) => {
+                    merge_versions(&mut table.versions, versions);
+                    table.overrides.push(path);
+                }
+                // a broken override shouldn't stop every command from running
+                Err(e) => eprintln!(
+                    "{} Ignoring {}: {:#}",
+                    style("⚠️").yellow(),
+                    path.display(),
+                    e
+                ),
+            }
+        }
+
+        table.versions.sort_by_key(|v| v.format);
+        table
+    })
+}
+
+pub fn supported_versions() -> &'static [SupportedVersion] {
+    &version_table().versions
 }
 
-pub const SUPPORTED_VERSIONS: &[SupportedVersion] = &[
-    SupportedVersion {
-        format: [101, 1],
-        label: "26.1 - 26.1.2",
-    },
-    SupportedVersion {
-        format: [107, 1],
-        label: "26.2",
-    },
-];
+fn load_versions_file(path: &Path) -> Result<Vec<SupportedVersion>> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    parse_versions(&content)
+}
+
+fn parse_versions(content: &str) -> Result<Vec<SupportedVersion>> {
+    let file: VersionsFile =
+        serde_json::from_str(content).context("Failed to parse versions.json")?;
+    file.versions
+        .into_iter()
+        .map(|entry| {
+            let format = parse_format_string(&entry.format).with_context(|| {
+                format!(
+                    "Invalid format '{}' in versions.json. Must be a decimal format (e.g. 101.1)",
+                    entry.format
+                )
+            })?;
+            Ok(SupportedVersion {
+                format,
+                label: entry.label,
+                renames: entry.renames,
+            })
+        })
+        .collect()
+}
+
+fn merge_versions(versions: &mut Vec<SupportedVersion>, overrides: Vec<SupportedVersion>) {
+    for version in overrides {
+        match versions.iter_mut().find(|v| v.format == version.format) {
+            Some(existing) => {
+                existing.label = version.label;
+                existing.renames.extend(version.renames);
+            }
+            None => versions.push(version),
+        }
+    }
+}
+
+// inclusive range of pack formats a pack declares, whichever pack.mcmeta scheme it uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatRange {
+    pub min: [u32; 2],
+    pub max: [u32; 2],
+}
+
+impl FormatRange {
+    pub fn single(format: [u32; 2]) -> Self {
+        FormatRange {
+            min: format,
+            max: format,
+        }
+    }
 
+    pub fn is_supported(&self) -> bool {
+        is_supported_format(self.min) && is_supported_format(self.max)
+    }
+
+    pub fn version_labels(&self) -> String {
+        version_label_range(self.min, self.max)
+    }
+
+    pub fn contains(&self, other: &FormatRange) -> bool {
+        self.min <= other.min && other.max <= self.max
+    }
+}
+
+impl fmt::Display for FormatRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.min == self.max {
+            write!(f, "{}", format_to_string(self.min))
+        } else {
+            write!(
+                f,
+                "{} - {}",
+                format_to_string(self.min),
+                format_to_string(self.max)
+            )
+        }
+    }
+}
+
+pub fn is_legacy_format(format: [u32; 2]) -> bool {
+    format < FIRST_DECIMAL_FORMAT
+}
+
+// legacy formats were plain integers, so they're shown without a minor version, as are upper
+// bounds that cover every minor version of a major one
 pub fn format_to_string(format: [u32; 2]) -> String {
-    format!("{}.{}", format[0], format[1])
+    if (is_legacy_format(format) && format[1] == 0) || format[1] == u32::MAX {
+        format[0].to_string()
+    } else {
+        format!("{}.{}", format[0], format[1])
+    }
 }
 
 pub fn parse_format_string(s: &str) -> Option<[u32; 2]> {
     let mut parts = s.split('.');
     let major = parts.next()?.parse::<u32>().ok()?;
-    let minor = parts.next()?.parse::<u32>().ok()?;
+    let minor = match parts.next() {
+        Some(minor) => minor.parse::<u32>().ok()?,
+        None => 0,
+    };
     if parts.next().is_some() {
         return None;
     }
     Some([major, minor])
 }
 
+// the version of a format; an upper bound covering a whole major version matches the
+// latest version of that major
 pub fn version_for_format(format: [u32; 2]) -> Option<&'static SupportedVersion> {
-    SUPPORTED_VERSIONS.iter().find(|v| v.format == format)
+    if format[1] == u32::MAX {
+        return supported_versions()
+            .iter()
+            .rev()
+            .find(|v| v.format[0] == format[0]);
+    }
+    supported_versions().iter().find(|v| v.format == format)
 }
 
 pub fn is_supported_format(format: [u32; 2]) -> bool {
@@ -37,7 +228,7 @@ pub fn is_supported_format(format: [u32; 2]) -> bool {
 }
 
 pub fn index_of_format(format: [u32; 2]) -> Option<usize> {
-    SUPPORTED_VERSIONS.iter().position(|v| v.format == format)
+    supported_versions().iter().position(|v| v.format == format)
 }
 
 pub fn cmp_format(a: [u32; 2], b: [u32; 2]) -> std::cmp::Ordering {
@@ -45,8 +236,8 @@ pub fn cmp_format(a: [u32; 2], b: [u32; 2]) -> std::cmp::Ordering {
 }
 
 pub fn version_label_range(min: [u32; 2], max: [u32; 2]) -> String {
-    let min_label = version_for_format(min).map(|v| v.label).unwrap_or("unknown");
-    let max_label = version_for_format(max).map(|v| v.label).unwrap_or("unknown");
+    let min_label = version_for_format(min).map_or("unknown", |v| v.label.as_str());
+    let max_label = version_for_format(max).map_or("unknown", |v| v.label.as_str());
     if min == max {
         min_label.to_string()
     } else {
@@ -54,10 +245,193 @@ pub fn version_label_range(min: [u32; 2], max: [u32; 2]) -> String {
     }
 }
 
-pub fn format_from_json(value: &serde_json::Value) -> Option<[u32; 2]> {
-    let arr = value.as_array()?;
-    if arr.len() != 2 {
-        return None;
+// a single format written as an integer, [major] or [major, minor]
+pub fn format_from_json(value: &Value) -> Option<[u32; 2]> {
+    match value {
+        Value::Number(n) => Some([n.as_u64()? as u32, 0]),
+        Value::Array(arr) => match arr.as_slice() {
+            [major] => Some([major.as_u64()? as u32, 0]),
+            [major, minor] => Some([major.as_u64()? as u32, minor.as_u64()? as u32]),
+            _ => None,
+        },
+        _ => None,
     }
-    Some([arr[0].as_u64()? as u32, arr[1].as_u64()? as u32])
-}
\ No newline at end of file
+}
+
+// upper bound of a min_format/max_format range: an integer or [major] covers every minor
+// version of that major
+pub fn max_format_from_json(value: &Value) -> Option<[u32; 2]> {
+    let major_only = match value {
+        Value::Number(_) => true,
+        Value::Array(arr) => arr.len() == 1,
+        _ => false,
+    };
+    let format = format_from_json(value)?;
+    Some(if major_only {
+        [format[0], u32::MAX]
+    } else {
+        format
+    })
+}
+
+// a format as written to pack.mcmeta: an integer for legacy formats and whole major versions,
+// [major, minor] otherwise
+pub fn format_to_json(format: [u32; 2]) -> Value {
+    if is_legacy_format(format) || format[1] == u32::MAX {
+        Value::from(format[0])
+    } else {
+        Value::from(format.to_vec())
+    }
+}
+
+// a legacy range of integer formats: a single integer, a [min, max] array or a
+// {"min_inclusive", "max_inclusive"} object
+pub fn legacy_range_from_json(value: &Value) -> Option<FormatRange> {
+    let legacy = |v: &Value| Some([v.as_u64()? as u32, 0]);
+    match value {
+        Value::Number(_) => legacy(value).map(FormatRange::single),
+        Value::Array(arr) => match arr.as_slice() {
+            [min, max] => Some(FormatRange {
+                min: legacy(min)?,
+                max: legacy(max)?,
+            }),
+            _ => None,
+        },
+        Value::Object(obj) => Some(FormatRange {
+            min: legacy(obj.get("min_inclusive")?)?,
+            max: legacy(obj.get("max_inclusive")?)?,
+        }),
+        _ => None,
+    }
+}
+
+// the formats a pack object in pack.mcmeta supports, preferring min_format/max_format, then
+// supported_formats, then pack_format
+pub fn format_range_from_pack(pack: &Value) -> Option<FormatRange> {
+    if let (Some(min), Some(max)) = (pack.get("min_format"), pack.get("max_format")) {
+        return Some(FormatRange {
+            min: format_from_json(min)?,
+            max: max_format_from_json(max)?,
+        });
+    }
+    if let Some(supported) = pack.get("supported_formats") {
+        return legacy_range_from_json(supported);
+    }
+    pack.get("pack_format")
+        .and_then(format_from_json)
+        .map(FormatRange::single)
+}
+
+// the formats an overlay entry applies to, from min_format/max_format or the legacy formats field
+pub fn format_range_from_overlay(entry: &Value) -> Option<FormatRange> {
+    if let (Some(min), Some(max)) = (entry.get("min_format"), entry.get("max_format")) {
+        return Some(FormatRange {
+            min: format_from_json(min)?,
+            max: max_format_from_json(max)?,
+        });
+    }
+    entry.get("formats").and_then(legacy_range_from_json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reads_legacy_ranges_in_every_shape() {
+        let range = |min, max| FormatRange {
+            min: [min, 0],
+            max: [max, 0],
+        };
+        assert_eq!(legacy_range_from_json(&json!(48)), Some(range(48, 48)));
+        assert_eq!(
+            legacy_range_from_json(&json!([41, 48])),
+            Some(range(41, 48))
+        );
+        assert_eq!(
+            legacy_range_from_json(&json!({"min_inclusive": 41, "max_inclusive": 48})),
+            Some(range(41, 48))
+        );
+        assert_eq!(legacy_range_from_json(&json!([41])), None);
+        assert_eq!(legacy_range_from_json(&json!({"min_inclusive": 41})), None);
+        assert_eq!(legacy_range_from_json(&json!("48")), None);
+    }
+
+    #[test]
+    fn prefers_min_and_max_format() {
+        let pack = json!({
+            "min_format": [88, 0],
+            "max_format": [94, 1],
+            "supported_formats": [41, 48],
+            "pack_format": 48
+        });
+        assert_eq!(
+            format_range_from_pack(&pack),
+            Some(FormatRange {
+                min: [88, 0],
+                max: [94, 1]
+            })
+        );
+    }
+
+    #[test]
+    fn treats_a_major_only_max_format_as_every_minor_version() {
+        let range = format_range_from_pack(&json!({"min_format": 101, "max_format": 107}));
+        assert_eq!(
+            range,
+            Some(FormatRange {
+                min: [101, 0],
+                max: [107, u32::MAX]
+            })
+        );
+        assert!(range.unwrap().contains(&FormatRange::single([107, 1])));
+        assert_eq!(
+            format_range_from_pack(&json!({"min_format": [101], "max_format": [107]}))
+                .map(|range| range.max),
+            Some([107, u32::MAX])
+        );
+    }
+
+    #[test]
+    fn falls_back_to_supported_formats_then_pack_format() {
+        assert_eq!(
+            format_range_from_pack(&json!({"pack_format": 48, "supported_formats": [41, 48]})),
+            Some(FormatRange {
+                min: [41, 0],
+                max: [48, 0]
+            })
+        );
+        assert_eq!(
+            format_range_from_pack(&json!({"pack_format": 48})),
+            Some(FormatRange::single([48, 0]))
+        );
+        assert_eq!(format_range_from_pack(&json!({"description": "x"})), None);
+    }
+
+    #[test]
+    fn reads_overlay_ranges() {
+        assert_eq!(
+            format_range_from_overlay(
+                &json!({"directory": "o", "min_format": [88, 0], "max_format": 94})
+            ),
+            Some(FormatRange {
+                min: [88, 0],
+                max: [94, u32::MAX]
+            })
+        );
+        assert_eq!(
+            format_range_from_overlay(
+                &json!({"directory": "o", "formats": {"min_inclusive": 41, "max_inclusive": 48}})
+            ),
+            Some(FormatRange {
+                min: [41, 0],
+                max: [48, 0]
+            })
+        );
+        assert_eq!(
+            format_range_from_overlay(&json!({"directory": "o", "min_format": 88})),
+            None
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use crate::config::{ProjectConfig, PROJECT_FILE};
+use crate::templates::PROJECT_DATA_DIR;
+use anyhow::{Context, Result};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::Path;
+
+// gitignore-style file in the pack root listing files to leave out of archives
+pub const IGNORE_FILE: &str = ".mcpackignore";
+
+// files that never belong in a distributed datapack
+pub const DEFAULT_IGNORES: &[&str] = &[
+    ".git/",
+    ".svn/",
+    ".hg/",
+    ".github/",
+    ".vscode/",
+    ".idea/",
+    ".DS_Store",
+    "Thumbs.db",
+    "desktop.ini",
+    "*.swp",
+    "*~",
+    ".gitignore",
+    ".gitattributes",
+    IGNORE_FILE,
+    PROJECT_FILE,
+    PROJECT_DATA_DIR,
+    "README*",
+    "*.md",
+    "*.sh",
+    "*.bat",
+    "*.ps1",
+];
+
+// decides which files in a pack folder are excluded from archives
+pub struct PackIgnore {
+    matcher: Gitignore,
+}
+
+impl PackIgnore {
+    // build the rules from the defaults, the pack's .mcpackignore, the ignore list in
+    // mcpack.toml and command line patterns, with later sources taking priority
+    pub fn load(root: &Path, include: &[String], exclude: &[String]) -> Result<Self> {
+        let mut builder = GitignoreBuilder::new(root);
+
+        for pattern in DEFAULT_IGNORES {
+            builder.add_line(None, pattern)?;
+        }
+
+        let ignore_file = root.join(IGNORE_FILE);
+        if ignore_file.exists() {
+            if let Some(err) = builder.add(&ignore_file) {
+                return Err(err).with_context(|| format!("Failed to read {}", IGNORE_FILE));
+            }
+        }
+
+        for pattern in &ProjectConfig::load(root)?.ignore {
+            builder.add_line(None, pattern).with_context(|| {
+                format!("Invalid ignore pattern in {}: '{}'", PROJECT_FILE, pattern)
+            })?;
+        }
+
+        for pattern in exclude {
+            builder
+                .add_line(None, pattern)
+                .with_context(|| format!("Invalid exclude pattern: '{}'", pattern))?;
+        }
+        for pattern in include {
+            builder
+                .add_line(None, &format!("!{}", pattern))
+                .with_context(|| format!("Invalid include pattern: '{}'", pattern))?;
+        }
+
+        Ok(PackIgnore {
+            matcher: builder.build().context("Failed to build ignore rules")?,
+        })
+    }
+
+    // check a path relative to the pack root
+    pub fn is_ignored(&self, relative: &Path, is_dir: bool) -> bool {
+        self.matcher
+            .matched_path_or_any_parents(relative, is_dir)
+            .is_ignore()
+    }
+}
@@ -0,0 +1,486 @@
+use crate::pack::Pack;
+use crate::pack_formats;
+use crate::registries;
+use crate::resource_location;
+use crate::validation::Diagnostic;
+use anyhow::Result;
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+// function tags the game provides even when no pack defines them
+pub const VANILLA_FUNCTION_TAGS: &[&str] = &["minecraft:load", "minecraft:tick"];
+
+// old registry folder names the pack may use for the formats it declares
+pub fn legacy_folders(pack: &mut Pack) -> Result<BTreeMap<String, String>> {
+    if !pack.contains("pack.mcmeta") {
+        return Ok(BTreeMap::new());
+    }
+    let range = serde_json::from_slice::<Value>(&pack.read("pack.mcmeta")?)
+        .ok()
+        .and_then(|mcmeta| {
+            mcmeta
+                .get("pack")
+                .and_then(pack_formats::format_range_from_pack)
+        });
+    Ok(registries::legacy_folders(range))
+}
+
+// every function and function tag defined by a pack, by resource location
+#[derive(Debug, Default)]
+pub struct PackIndex {
+    pub functions: HashSet<String>,
+    pub function_tags: HashSet<String>,
+    // old registry folder names the pack may still use, see registries::legacy_folders
+    pub legacy_folders: BTreeMap<String, String>,
+}
+
+impl PackIndex {
+    pub fn build(pack: &Pack, legacy_folders: BTreeMap<String, String>) -> Self {
+        let mut index = PackIndex {
+            legacy_folders,
+            ..PackIndex::default()
+        };
+        for path in pack.files() {
+            match index.resource_location(path) {
+                Some((registry, id)) if registry == "function" => {
+                    index.functions.insert(id);
+                }
+                Some((registry, id)) if registry == "tags/function" => {
+                    index.function_tags.insert(id);
+                }
+                _ => {}
+            }
+        }
+        index
+    }
+
+    // registry and resource location of a pack file, reading renamed registry folders
+    // under their current names
+    pub fn resource_location(&self, path: &str) -> Option<(String, String)> {
+        registries::resource_location_for_path(&self.current_path(path))
+    }
+
+    // a pack file path with a renamed registry folder replaced by its current name
+    pub fn current_path<'a>(&self, path: &'a str) -> Cow<'a, str> {
+        let Some((namespace, relative)) = path
+            .strip_prefix("data/")
+            .and_then(|rest| rest.split_once('/'))
+        else {
+            return Cow::Borrowed(path);
+        };
+        match registries::current_folder_path(relative, &self.legacy_folders) {
+            Cow::Borrowed(_) => Cow::Borrowed(path),
+            Cow::Owned(relative) => Cow::Owned(format!("data/{}/{}", namespace, relative)),
+        }
+    }
+
+    pub fn has_function(&self, id: &str) -> bool {
+        self.functions.contains(&resource_location::normalize(id))
+    }
+
+    pub fn has_function_tag(&self, id: &str) -> bool {
+        let id = resource_location::normalize(id);
+        self.function_tags.contains(&id) || VANILLA_FUNCTION_TAGS.contains(&id.as_str())
+    }
+}
+
+// a `function` reference found in an mcfunction file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionCall {
+    pub line: usize,
+    pub target: String,
+    pub is_tag: bool,
+}
+
+// find every function or function tag referenced by an mcfunction file, covering
+// `function`, `execute ... run function`, `execute if function`, `schedule function`
+// and `schedule clear`
+pub fn parse_function_calls(content: &str) -> Vec<FunctionCall> {
+    let mut calls = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        // macro lines are prefixed with '$'
+        let line = line.strip_prefix('$').unwrap_or(line);
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        for (t, token) in tokens.iter().enumerate() {
+            let previous = t.checked_sub(1).map(|p| tokens[p]);
+            let is_function_keyword = *token == "function"
+                && matches!(previous, None | Some("run" | "schedule" | "if" | "unless"));
+            let is_schedule_clear = *token == "clear" && previous == Some("schedule");
+            if !is_function_keyword && !is_schedule_clear {
+                continue;
+            }
+
+            let Some(target) = tokens.get(t + 1) else {
+                continue;
+            };
+            // targets built from macro arguments can't be resolved statically
+            if target.contains("$(") {
+                continue;
+            }
+
+            let (target, is_tag) = match target.strip_prefix('#') {
+                Some(tag) => (tag, true),
+                None => (*target, false),
+            };
+            calls.push(FunctionCall {
+                line: i + 1,
+                target: target.to_string(),
+                is_tag,
+            });
+        }
+    }
+
+    calls
+}
+
+// report function calls that don't resolve to a function or function tag
+pub fn check_function_calls(pack: &mut Pack, index: &PackIndex) -> Result<Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+
+    let function_files: Vec<String> = pack
+        .files()
+        .iter()
+        .filter(|path| {
+            index
+                .resource_location(path)
+                .is_some_and(|(registry, _)| registry == "function")
+        })
+        .cloned()
+        .collect();
+
+    for path in function_files {
+        let content = String::from_utf8_lossy(&pack.read(&path)?).to_string();
+
+        for call in parse_function_calls(&content) {
+            let prefix = if call.is_tag { "#" } else { "" };
+            let message = if !resource_location::is_valid(&call.target) {
+                format!("invalid function reference '{}{}'", prefix, call.target)
+            } else if call.is_tag && !index.has_function_tag(&call.target) {
+                format!(
+                    "function tag '#{}' does not exist",
+                    resource_location::normalize(&call.target)
+                )
+            } else if !call.is_tag && !index.has_function(&call.target) {
+                format!(
+                    "function '{}' does not exist",
+                    resource_location::normalize(&call.target)
+                )
+            } else {
+                continue;
+            };
+            diagnostics.push(Diagnostic::error(&path, message).at_line(call.line));
+        }
+    }
+
+    Ok(diagnostics)
+}
+
+// an entry in a tag's `values` list, either "id", "#tag" or {"id": ..., "required": ...}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagEntry {
+    pub id: String,
+    pub is_tag: bool,
+    pub required: bool,
+}
+
+// read a single entry of a tag's `values` list
+pub fn parse_tag_entry(value: &Value) -> Result<TagEntry, String> {
+    let (raw, required) = match value {
+        Value::String(s) => (s.as_str(), true),
+        Value::Object(obj) => {
+            let id = obj
+                .get("id")
+                .and_then(|id| id.as_str())
+                .ok_or("tag entry object is missing a string 'id'")?;
+            let required = match obj.get("required") {
+                None => true,
+                Some(Value::Bool(b)) => *b,
+                Some(_) => return Err("'required' must be a boolean".to_string()),
+            };
+            (id, required)
+        }
+        _ => return Err(format!("invalid tag entry: {}", value)),
+    };
+    let (id, is_tag) = match raw.strip_prefix('#') {
+        Some(tag) => (tag, true),
+        None => (raw, false),
+    };
+    Ok(TagEntry {
+        id: id.to_string(),
+        is_tag,
+        required,
+    })
+}
+
+// report function tag entries that don't resolve and tags that include each other
+pub fn check_function_tags(pack: &mut Pack, index: &PackIndex) -> Result<Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+
+    let tag_files: Vec<(String, String)> = pack
+        .files()
+        .iter()
+        .filter_map(|path| match index.resource_location(path) {
+            Some((registry, id)) if registry == "tags/function" => Some((path.clone(), id)),
+            _ => None,
+        })
+        .collect();
+
+    // tag -> (file, tags it includes) for cycle detection
+    let mut graph: BTreeMap<String, (String, Vec<String>)> = BTreeMap::new();
+
+    for (path, tag_id) in tag_files {
+        // invalid JSON is already reported by the structural checks
+        let Ok(tag) = serde_json::from_slice::<Value>(&pack.read(&path)?) else {
+            continue;
+        };
+
+        let Some(values) = tag.get("values").and_then(|v| v.as_array()) else {
+            diagnostics.push(Diagnostic::error(&path, "missing 'values' array"));
+            continue;
+        };
+
+        let mut children = Vec::new();
+        for value in values {
+            let entry = match parse_tag_entry(value) {
+                Ok(entry) => entry,
+                Err(message) => {
+                    diagnostics.push(Diagnostic::error(&path, message));
+                    continue;
+                }
+            };
+            let prefix = if entry.is_tag { "#" } else { "" };
+            let id = resource_location::normalize(&entry.id);
+            if !resource_location::is_valid(&entry.id) {
+                diagnostics.push(Diagnostic::error(
+                    &path,
+                    format!("invalid tag entry '{}{}'", prefix, entry.id),
+                ));
+                continue;
+            }
+
+            let exists = if entry.is_tag {
+                index.has_function_tag(&id)
+            } else {
+                index.has_function(&id)
+            };
+            if !exists {
+                let kind = if entry.is_tag {
+                    "function tag"
+                } else {
+                    "function"
+                };
+                let message = format!("{} '{}{}' does not exist", kind, prefix, id);
+                // optional entries are skipped by the game when missing
+                if entry.required {
+                    diagnostics.push(Diagnostic::error(&path, message));
+                } else {
+                    diagnostics.push(Diagnostic::warning(
+                        &path,
+                        format!("{} (entry is optional)", message),
+                    ));
+                }
+            }
+
+            if entry.is_tag {
+                children.push(id);
+            }
+        }
+        graph.insert(tag_id, (path, children));
+    }
+
+    for cycle in find_tag_cycles(&graph) {
+        let (path, _) = &graph[&cycle[0]];
+        let chain = cycle
+            .iter()
+            .chain(std::iter::once(&cycle[0]))
+            .map(|id| format!("#{}", id))
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        diagnostics.push(Diagnostic::error(
+            path,
+            format!("function tags include each other: {}", chain),
+        ));
+    }
+
+    Ok(diagnostics)
+}
+
+// find every distinct cycle in a tag graph with a depth-first search
+fn find_tag_cycles(graph: &BTreeMap<String, (String, Vec<String>)>) -> Vec<Vec<String>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum State {
+        Visiting,
+        Done,
+    }
+
+    fn visit(
+        id: &str,
+        graph: &BTreeMap<String, (String, Vec<String>)>,
+        states: &mut HashMap<String, State>,
+        stack: &mut Vec<String>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        states.insert(id.to_string(), State::Visiting);
+        stack.push(id.to_string());
+
+        if let Some((_, children)) = graph.get(id) {
+            for child in children {
+                match states.get(child) {
+                    Some(State::Visiting) => {
+                        let start = stack.iter().position(|s| s == child).unwrap();
+                        cycles.push(stack[start..].to_vec());
+                    }
+                    Some(State::Done) => {}
+                    None => visit(child, graph, states, stack, cycles),
+                }
+            }
+        }
+
+        stack.pop();
+        states.insert(id.to_string(), State::Done);
+    }
+
+    let mut states = HashMap::new();
+    let mut cycles = Vec::new();
+    for id in graph.keys() {
+        if !states.contains_key(id) {
+            visit(id, graph, &mut states, &mut Vec::new(), &mut cycles);
+        }
+    }
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets(content: &str) -> Vec<(usize, String, bool)> {
+        parse_function_calls(content)
+            .into_iter()
+            .map(|call| (call.line, call.target, call.is_tag))
+            .collect()
+    }
+
+    #[test]
+    fn parses_plain_and_tag_calls() {
+        let content = "function ns:a\nfunction #ns:tag\n";
+        assert_eq!(
+            targets(content),
+            vec![
+                (1, "ns:a".to_string(), false),
+                (2, "ns:tag".to_string(), true)
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_calls_inside_execute_and_schedule() {
+        let content = "execute as @a run function ns:run\n\
+                       execute if function ns:check run say hi\n\
+                       schedule function ns:later 1t\n\
+                       schedule clear ns:later\n";
+        assert_eq!(
+            targets(content),
+            vec![
+                (1, "ns:run".to_string(), false),
+                (2, "ns:check".to_string(), false),
+                (3, "ns:later".to_string(), false),
+                (4, "ns:later".to_string(), false)
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_comments_and_macro_targets() {
+        let content = "# function ns:commented\n$function $(target)\n$function ns:macro_line\n";
+        assert_eq!(
+            targets(content),
+            vec![(3, "ns:macro_line".to_string(), false)]
+        );
+    }
+
+    #[test]
+    fn ignores_function_as_an_argument() {
+        assert!(targets("say function ns:a\ntellraw @a \"function\"\n").is_empty());
+    }
+
+    fn graph(edges: &[(&str, &[&str])]) -> BTreeMap<String, (String, Vec<String>)> {
+        edges
+            .iter()
+            .map(|(id, children)| {
+                (
+                    id.to_string(),
+                    (
+                        format!("{id}.json"),
+                        children.iter().map(|c| c.to_string()).collect(),
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn finds_no_cycles_in_a_tree() {
+        let graph = graph(&[
+            ("ns:a", &["ns:b", "ns:c"]),
+            ("ns:b", &["ns:c"]),
+            ("ns:c", &[]),
+        ]);
+        assert!(find_tag_cycles(&graph).is_empty());
+    }
+
+    #[test]
+    fn finds_self_and_indirect_cycles() {
+        let graph = graph(&[
+            ("ns:a", &["ns:b"]),
+            ("ns:b", &["ns:c"]),
+            ("ns:c", &["ns:a"]),
+            ("ns:self", &["ns:self"]),
+        ]);
+        assert_eq!(
+            find_tag_cycles(&graph),
+            vec![
+                vec!["ns:a".to_string(), "ns:b".to_string(), "ns:c".to_string()],
+                vec!["ns:self".to_string()]
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_references_to_unknown_tags() {
+        let graph = graph(&[("ns:a", &["ns:missing"])]);
+        assert!(find_tag_cycles(&graph).is_empty());
+    }
+
+    #[test]
+    fn reads_renamed_registry_folders_under_their_current_names() {
+        let index = PackIndex {
+            legacy_folders: BTreeMap::from([
+                ("functions".to_string(), "function".to_string()),
+                ("tags/functions".to_string(), "tags/function".to_string()),
+            ]),
+            ..PackIndex::default()
+        };
+        assert_eq!(
+            index.resource_location("data/ns/functions/a/b.mcfunction"),
+            Some(("function".to_string(), "ns:a/b".to_string()))
+        );
+        assert_eq!(
+            index.resource_location("data/ns/tags/functions/t.json"),
+            Some(("tags/function".to_string(), "ns:t".to_string()))
+        );
+        assert_eq!(
+            index.current_path("data/ns/function/a.mcfunction"),
+            "data/ns/function/a.mcfunction"
+        );
+        assert_eq!(index.current_path("pack.mcmeta"), "pack.mcmeta");
+    }
+}
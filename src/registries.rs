@@ -0,0 +1,363 @@
+use crate::pack_formats::{self, FormatRange};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+pub struct Registry {
+    pub name: &'static str,
+    pub extension: &'static str,
+    pub label: &'static str,
+}
+
+// registry folders a datapack namespace can contain, the file extension each one holds
+// and the name used when displaying them
+pub const REGISTRIES: &[Registry] = &[
+    Registry {
+        name: "advancement",
+        extension: ".json",
+        label: "Advancements",
+    },
+    Registry {
+        name: "banner_pattern",
+        extension: ".json",
+        label: "Banner Patterns",
+    },
+    Registry {
+        name: "cat_variant",
+        extension: ".json",
+        label: "Cat Variants",
+    },
+    Registry {
+        name: "chat_type",
+        extension: ".json",
+        label: "Chat Types",
+    },
+    Registry {
+        name: "chicken_variant",
+        extension: ".json",
+        label: "Chicken Variants",
+    },
+    Registry {
+        name: "cow_variant",
+        extension: ".json",
+        label: "Cow Variants",
+    },
+    Registry {
+        name: "damage_type",
+        extension: ".json",
+        label: "Damage Types",
+    },
+    Registry {
+        name: "dialog",
+        extension: ".json",
+        label: "Dialogs",
+    },
+    Registry {
+        name: "dimension",
+        extension: ".json",
+        label: "Dimensions",
+    },
+    Registry {
+        name: "dimension_type",
+        extension: ".json",
+        label: "Dimension Types",
+    },
+    Registry {
+        name: "enchantment",
+        extension: ".json",
+        label: "Enchantments",
+    },
+    Registry {
+        name: "enchantment_provider",
+        extension: ".json",
+        label: "Enchantment Providers",
+    },
+    Registry {
+        name: "frog_variant",
+        extension: ".json",
+        label: "Frog Variants",
+    },
+    Registry {
+        name: "function",
+        extension: ".mcfunction",
+        label: "Functions",
+    },
+    Registry {
+        name: "instrument",
+        extension: ".json",
+        label: "Instruments",
+    },
+    Registry {
+        name: "item_modifier",
+        extension: ".json",
+        label: "Item Modifiers",
+    },
+    Registry {
+        name: "jukebox_song",
+        extension: ".json",
+        label: "Jukebox Songs",
+    },
+    Registry {
+        name: "loot_table",
+        extension: ".json",
+        label: "Loot Tables",
+    },
+    Registry {
+        name: "painting_variant",
+        extension: ".json",
+        label: "Painting Variants",
+    },
+    Registry {
+        name: "pig_variant",
+        extension: ".json",
+        label: "Pig Variants",
+    },
+    Registry {
+        name: "predicate",
+        extension: ".json",
+        label: "Predicates",
+    },
+    Registry {
+        name: "recipe",
+        extension: ".json",
+        label: "Recipes",
+    },
+    Registry {
+        name: "structure",
+        extension: ".nbt",
+        label: "Structures",
+    },
+    Registry {
+        name: "test_environment",
+        extension: ".json",
+        label: "Test Environments",
+    },
+    Registry {
+        name: "test_instance",
+        extension: ".json",
+        label: "Test Instances",
+    },
+    Registry {
+        name: "trial_spawner",
+        extension: ".json",
+        label: "Trial Spawners",
+    },
+    Registry {
+        name: "trim_material",
+        extension: ".json",
+        label: "Trim Materials",
+    },
+    Registry {
+        name: "trim_pattern",
+        extension: ".json",
+        label: "Trim Patterns",
+    },
+    Registry {
+        name: "wolf_sound_variant",
+        extension: ".json",
+        label: "Wolf Sound Variants",
+    },
+    Registry {
+        name: "wolf_variant",
+        extension: ".json",
+        label: "Wolf Variants",
+    },
+    Registry {
+        name: "worldgen/biome",
+        extension: ".json",
+        label: "Worldgen Biomes",
+    },
+    Registry {
+        name: "worldgen/configured_carver",
+        extension: ".json",
+        label: "Worldgen Configured Carvers",
+    },
+    Registry {
+        name: "worldgen/configured_feature",
+        extension: ".json",
+        label: "Worldgen Configured Features",
+    },
+    Registry {
+        name: "worldgen/density_function",
+        extension: ".json",
+        label: "Worldgen Density Functions",
+    },
+    Registry {
+        name: "worldgen/flat_level_generator_preset",
+        extension: ".json",
+        label: "Worldgen Flat Level Generator Presets",
+    },
+    Registry {
+        name: "worldgen/multi_noise_biome_source_parameter_list",
+        extension: ".json",
+        label: "Worldgen Multi Noise Biome Source Parameter Lists",
+    },
+    Registry {
+        name: "worldgen/noise",
+        extension: ".json",
+        label: "Worldgen Noises",
+    },
+    Registry {
+        name: "worldgen/noise_settings",
+        extension: ".json",
+        label: "Worldgen Noise Settings",
+    },
+    Registry {
+        name: "worldgen/placed_feature",
+        extension: ".json",
+        label: "Worldgen Placed Features",
+    },
+    Registry {
+        name: "worldgen/processor_list",
+        extension: ".json",
+        label: "Worldgen Processor Lists",
+    },
+    Registry {
+        name: "worldgen/structure",
+        extension: ".json",
+        label: "Worldgen Structures",
+    },
+    Registry {
+        name: "worldgen/structure_set",
+        extension: ".json",
+        label: "Worldgen Structure Sets",
+    },
+    Registry {
+        name: "worldgen/template_pool",
+        extension: ".json",
+        label: "Worldgen Template Pools",
+    },
+    Registry {
+        name: "worldgen/world_preset",
+        extension: ".json",
+        label: "Worldgen World Presets",
+    },
+];
+
+// find the registry a file belongs to from its path inside a namespace folder
+// (e.g. "worldgen/biome/plains.json" or "tags/block/ores.json")
+pub fn registry_for_path(relative: &str) -> Option<String> {
+    if let Some(tag_path) = relative.strip_prefix("tags/") {
+        // tags can target any registry, including ones datapacks can't add entries to
+        let registry = match find_registry(tag_path) {
+            Some(registry) => registry.name.to_string(),
+            None => tag_path.split_once('/')?.0.to_string(),
+        };
+        return Some(format!("tags/{}", registry));
+    }
+
+    find_registry(relative).map(|registry| registry.name.to_string())
+}
+
+// whether a name is a registry folder, including tags of any registry
+pub fn is_registry(name: &str) -> bool {
+    match name.strip_prefix("tags/") {
+        Some(tag_registry) => {
+            !tag_registry.is_empty()
+                && tag_registry
+                    .split('/')
+                    .all(|part| !part.is_empty() && part != "." && part != "..")
+        }
+        None => REGISTRIES.iter().any(|r| r.name == name),
+    }
+}
+
+// file extension expected for files in a registry
+pub fn extension_for_registry(registry: &str) -> &'static str {
+    if registry.starts_with("tags/") {
+        return ".json";
+    }
+    REGISTRIES
+        .iter()
+        .find(|r| r.name == registry)
+        .map(|r| r.extension)
+        .unwrap_or(".json")
+}
+
+// display name for a registry, e.g. "Loot Tables" or "Block Tags"
+pub fn label_for_registry(registry: &str) -> String {
+    if let Some(tag_registry) = registry.strip_prefix("tags/") {
+        let name = match REGISTRIES.iter().find(|r| r.name == tag_registry) {
+            Some(r) => r.name.rsplit('/').next().unwrap_or(r.name).to_string(),
+            None => tag_registry.to_string(),
+        };
+        let words = name
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" ");
+        return format!("{} Tags", words);
+    }
+
+    REGISTRIES
+        .iter()
+        .find(|r| r.name == registry)
+        .map(|r| r.label.to_string())
+        .unwrap_or_else(|| registry.to_string())
+}
+
+// registry folders renamed by versions newer than the oldest format a pack declares, from
+// their old names to the current ones (e.g. "functions" to "function" for packs made before
+// 1.21), so files in those folders are still recognised
+pub fn legacy_folders(range: Option<FormatRange>) -> BTreeMap<String, String> {
+    let Some(range) = range else {
+        return BTreeMap::new();
+    };
+    pack_formats::supported_versions()
+        .iter()
+        .filter(|v| v.format > range.min)
+        .flat_map(|v| v.renames.clone())
+        .collect()
+}
+
+// a path inside a namespace folder with a renamed registry folder replaced by its current
+// name, e.g. "functions/main.mcfunction" becomes "function/main.mcfunction"
+pub fn current_folder_path<'a>(
+    relative: &'a str,
+    legacy_folders: &BTreeMap<String, String>,
+) -> Cow<'a, str> {
+    for (old, new) in legacy_folders {
+        if let Some(rest) = relative
+            .strip_prefix(old.as_str())
+            .and_then(|rest| rest.strip_prefix('/'))
+        {
+            return Cow::Owned(format!("{}/{}", new, rest));
+        }
+    }
+    Cow::Borrowed(relative)
+}
+
+fn find_registry(relative: &str) -> Option<&'static Registry> {
+    REGISTRIES.iter().find(|registry| {
+        relative
+            .strip_prefix(registry.name)
+            .is_some_and(|rest| rest.starts_with('/'))
+    })
+}
+
+// registry and resource location of a pack file (e.g. "data/ns/function/a/b.mcfunction"
+// belongs to "function" as "ns:a/b")
+pub fn resource_location_for_path(path: &str) -> Option<(String, String)> {
+    let (namespace, relative) = path.strip_prefix("data/")?.split_once('/')?;
+    let registry = registry_for_path(relative)?;
+    let element_path =
+        relative[registry.len() + 1..].strip_suffix(extension_for_registry(&registry))?;
+    Some((registry, format!("{}:{}", namespace, element_path)))
+}
+
+// pack file path for a resource location in a registry
+pub fn path_for_resource_location(registry: &str, id: &str) -> String {
+    let (namespace, path) = crate::resource_location::split(id);
+    format!(
+        "data/{}/{}/{}{}",
+        namespace,
+        registry,
+        path,
+        extension_for_registry(registry)
+    )
+}
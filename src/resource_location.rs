@@ -0,0 +1,41 @@
+// characters allowed in the namespace part of a resource location
+pub fn is_namespace_char(c: char) -> bool {
+    matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.')
+}
+
+// characters allowed in the path part of a resource location
+pub fn is_path_char(c: char) -> bool {
+    is_namespace_char(c) || c == '/'
+}
+
+// first character that isn't allowed in a namespace, if any
+pub fn invalid_namespace_char(namespace: &str) -> Option<char> {
+    namespace.chars().find(|c| !is_namespace_char(*c))
+}
+
+// first character that isn't allowed in a resource location path, if any
+pub fn invalid_path_char(path: &str) -> Option<char> {
+    path.chars().find(|c| !is_path_char(*c))
+}
+
+// split a resource location into namespace and path, defaulting to the minecraft namespace
+pub fn split(id: &str) -> (&str, &str) {
+    match id.split_once(':') {
+        Some(("", path)) => ("minecraft", path),
+        Some(parts) => parts,
+        None => ("minecraft", id),
+    }
+}
+
+// fully qualify a resource location (e.g. "load" becomes "minecraft:load")
+pub fn normalize(id: &str) -> String {
+    let (namespace, path) = split(id);
+    format!("{}:{}", namespace, path)
+}
+
+pub fn is_valid(id: &str) -> bool {
+    let (namespace, path) = split(id);
+    !path.is_empty()
+        && invalid_namespace_char(namespace).is_none()
+        && invalid_path_char(path).is_none()
+}
@@ -0,0 +1,577 @@
+use crate::pack::Pack;
+use crate::references::PackIndex;
+use crate::registries;
+use crate::resource_location;
+use anyhow::{Context, Result};
+use console::style;
+use similar::{ChangeTag, TextDiff};
+use std::fs;
+use std::path::Path;
+
+// a planned change to one file of a pack folder: a move, new content, or both
+#[derive(Debug, Clone)]
+pub struct FileChange {
+    pub path: String,
+    pub new_path: String,
+    // text before and after rewriting, when the content changes
+    pub content: Option<(String, String)>,
+}
+
+impl FileChange {
+    pub fn is_move(&self) -> bool {
+        self.path != self.new_path
+    }
+}
+
+// rename a namespace in every resource location of a JSON or mcfunction file, e.g. "old:foo"
+// and "#old:bar" become "new:foo" and "#new:bar" while "gold:foo" is left alone
+pub fn replace_namespace(content: &str, old: &str, new: &str) -> String {
+    let pattern = format!("{}:", old);
+    let mut result = String::with_capacity(content.len());
+    let mut last = 0;
+
+    for (index, _) in content.match_indices(&pattern) {
+        let at_boundary = content[..index]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !resource_location::is_path_char(c));
+        if at_boundary {
+            result.push_str(&content[last..index]);
+            result.push_str(new);
+            result.push(':');
+            last = index + pattern.len();
+        }
+    }
+
+    result.push_str(&content[last..]);
+    result
+}
+
+// replace whole references to a resource location, so "ns:a/b" matches but "ns:a/bc" and
+// "ns:a/b/c" don't; tag references only match with a '#' prefix, others only without one
+pub fn replace_resource_location(content: &str, old: &str, new: &str, is_tag: bool) -> String {
+    replace_at(content, &reference_indices(content, old, is_tag), old, new)
+}
+
+// replace the references to an element of a registry in a pack file, leaving alone the ones
+// whose context shows they belong to another registry, such as an advancement that shares
+// the id of its reward function
+pub fn replace_element_references(
+    path: &str,
+    content: &str,
+    registry: &str,
+    old: &str,
+    new: &str,
+) -> String {
+    let is_tag = registry.starts_with("tags/");
+    let indices: Vec<usize> = reference_indices(content, old, is_tag)
+        .into_iter()
+        .filter(|&index| {
+            reference_registry(path, content, index, is_tag).is_none_or(|other| other == registry)
+        })
+        .collect();
+    replace_at(content, &indices, old, new)
+}
+
+fn replace_at(content: &str, indices: &[usize], old: &str, new: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut last = 0;
+
+    for &index in indices {
+        result.push_str(&content[last..index]);
+        result.push_str(new);
+        last = index + old.len();
+    }
+
+    result.push_str(&content[last..]);
+    result
+}
+
+// the registry a reference at a byte offset refers to, when its context tells: entries of a
+// tag file belong to the tag's registry, command arguments to the registry the command takes
+// and JSON values to the field they're in (e.g. an advancement's "function" reward)
+fn reference_registry(path: &str, content: &str, index: usize, is_tag: bool) -> Option<String> {
+    let before = &content[..index];
+    let before = if is_tag {
+        before.strip_suffix('#')?
+    } else {
+        before
+    };
+
+    let registry = if path.ends_with(".mcfunction") {
+        let line = &before[before.rfind('\n').map_or(0, |i| i + 1)..];
+        command_argument_registry(line)?
+    } else if let Some((registry, _)) = registries::resource_location_for_path(path)
+        .filter(|(registry, _)| registry.starts_with("tags/"))
+    {
+        // tag entries are elements of the tagged registry, or other tags of it with a '#'
+        return Some(if is_tag {
+            registry
+        } else {
+            registry["tags/".len()..].to_string()
+        });
+    } else {
+        json_field_registry(json_key_before(before.strip_suffix('"')?)?)?
+    };
+
+    Some(if is_tag {
+        format!("tags/{}", registry)
+    } else {
+        registry.to_string()
+    })
+}
+
+// the registry of the command argument a line of a function ends with, e.g. "function",
+// "execute if predicate" or "advancement revoke @s only"
+fn command_argument_registry(line: &str) -> Option<&'static str> {
+    let words: Vec<&str> = line
+        .trim_start_matches('$')
+        .split_whitespace()
+        // commands inside JSON text, such as a "/function" click event
+        .map(|word| word.trim_start_matches(|c: char| !c.is_ascii_alphabetic()))
+        .collect();
+    let command = match words.iter().rposition(|word| *word == "run") {
+        Some(run) => &words[run + 1..],
+        None => &words[..],
+    };
+
+    match (command, words.last()?) {
+        (_, &"function") => Some("function"),
+        ([.., "schedule", "clear"], _) => Some("function"),
+        (_, &"predicate") => Some("predicate"),
+        (["advancement", ..], &("only" | "from" | "through" | "until")) => Some("advancement"),
+        (["recipe", "give" | "take", _], _) => Some("recipe"),
+        (["loot", ..], &("loot" | "fish")) => Some("loot_table"),
+        (["item", "modify", ..], _) => Some("item_modifier"),
+        (["place", "feature"], _) => Some("worldgen/configured_feature"),
+        (["place", "structure"], _) => Some("worldgen/structure"),
+        (["place", "template"], _) => Some("structure"),
+        _ => None,
+    }
+}
+
+// the registry a JSON field refers to, for fields that always hold references to one
+fn json_field_registry(key: &str) -> Option<&'static str> {
+    match key {
+        "function" => Some("function"),
+        "loot" => Some("loot_table"),
+        "recipes" => Some("recipe"),
+        _ => None,
+    }
+}
+
+// the key of the JSON field a string value belongs to, given the text before the value's
+// opening quote; values inside an array belong to the array's field
+fn json_key_before(before: &str) -> Option<&str> {
+    let mut rest = before.trim_end();
+    // skip earlier values of the same array
+    while let Some(previous) = rest.strip_suffix(',') {
+        let previous = previous.trim_end().strip_suffix('"')?;
+        rest = previous[..previous.rfind('"')?].trim_end();
+    }
+    let rest = rest.strip_suffix('[').map_or(rest, str::trim_end);
+    let key = rest.strip_suffix(':')?.trim_end().strip_suffix('"')?;
+    Some(&key[key.rfind('"')? + 1..])
+}
+
+// line numbers of every reference to a resource location, matched like
+// replace_resource_location does
+pub fn find_resource_location(content: &str, id: &str, is_tag: bool) -> Vec<usize> {
+    let mut lines: Vec<usize> = reference_indices(content, id, is_tag)
+        .into_iter()
+        .map(|index| content[..index].matches('\n').count() + 1)
+        .collect();
+    lines.dedup();
+    lines
+}
+
+// byte offsets of whole references to a resource location
+fn reference_indices(content: &str, id: &str, is_tag: bool) -> Vec<usize> {
+    content
+        .match_indices(id)
+        .map(|(index, _)| index)
+        .filter(|&index| {
+            let mut before = content[..index].chars().rev();
+            let at_start = match before.next() {
+                Some('#') => is_tag && before.next().is_none_or(|c| !is_reference_char(c)),
+                Some(c) => !is_tag && !is_reference_char(c),
+                None => !is_tag,
+            };
+            let at_end = content[index + id.len()..]
+                .chars()
+                .next()
+                .is_none_or(|c| !is_reference_char(c));
+            at_start && at_end
+        })
+        .collect()
+}
+
+fn is_reference_char(c: char) -> bool {
+    resource_location::is_path_char(c) || c == ':' || c == '#'
+}
+
+// move a pack file from data/<old>/ to data/<new>/, including files inside overlay folders
+pub fn replace_namespace_in_path(path: &str, old: &str, new: &str) -> String {
+    let mut segments: Vec<&str> = path.split('/').collect();
+    // data/ is either at the pack root or directly inside an overlay folder
+    for i in 1..segments.len().min(3) {
+        if segments[i - 1] == "data" && segments[i] == old && i + 1 < segments.len() {
+            segments[i] = new;
+        }
+    }
+    segments.join("/")
+}
+
+// whether a pack file holds resource locations that should be rewritten
+pub fn is_rewritable(path: &str) -> bool {
+    path.ends_with(".json") || path.ends_with(".mcfunction")
+}
+
+// plan moving data/<old>/ to data/<new>/ and rewriting every reference to the old namespace
+pub fn plan_namespace_rename(pack: &mut Pack, old: &str, new: &str) -> Result<Vec<FileChange>> {
+    let mut changes = Vec::new();
+
+    let files = pack.files().to_vec();
+    for path in files {
+        let new_path = replace_namespace_in_path(&path, old, new);
+        let content = if is_rewritable(&path) {
+            let before = String::from_utf8_lossy(&pack.read(&path)?).to_string();
+            let after = replace_namespace(&before, old, new);
+            (before != after).then_some((before, after))
+        } else {
+            None
+        };
+
+        if new_path != path || content.is_some() {
+            changes.push(FileChange {
+                path,
+                new_path,
+                content,
+            });
+        }
+    }
+
+    Ok(changes)
+}
+
+// plan moving one element to a new resource location and rewriting every reference to it;
+// an element in a renamed registry folder stays in the folder name the pack uses
+pub fn plan_element_move(
+    pack: &mut Pack,
+    index: &PackIndex,
+    registry: &str,
+    old_id: &str,
+    new_id: &str,
+) -> Result<Vec<FileChange>> {
+    let find = |id: &str| {
+        pack.files()
+            .iter()
+            .find(|path| {
+                index
+                    .resource_location(path)
+                    .is_some_and(|(other, other_id)| other == registry && other_id == id)
+            })
+            .cloned()
+    };
+    let Some(old_path) = find(old_id) else {
+        anyhow::bail!(
+            "{} '{}' not found ({})",
+            registry,
+            old_id,
+            registries::path_for_resource_location(registry, old_id)
+        );
+    };
+    if let Some(existing) = find(new_id) {
+        anyhow::bail!("{} '{}' already exists ({})", registry, new_id, existing);
+    }
+
+    let (old_namespace, old_element) = resource_location::split(old_id);
+    let (new_namespace, new_element) = resource_location::split(new_id);
+    let extension = registries::extension_for_registry(registry);
+    let folder = old_path
+        .strip_prefix(&format!("data/{}/", old_namespace))
+        .and_then(|rest| rest.strip_suffix(&format!("/{}{}", old_element, extension)))
+        .unwrap_or(registry);
+    let new_path = format!(
+        "data/{}/{}/{}{}",
+        new_namespace, folder, new_element, extension
+    );
+
+    let mut changes = Vec::new();
+
+    let files = pack.files().to_vec();
+    for path in files {
+        let content = if is_rewritable(&path) {
+            let before = String::from_utf8_lossy(&pack.read(&path)?).to_string();
+            let after = replace_element_references(
+                &index.current_path(&path),
+                &before,
+                registry,
+                old_id,
+                new_id,
+            );
+            (before != after).then_some((before, after))
+        } else {
+            None
+        };
+
+        let target = if path == old_path {
+            new_path.clone()
+        } else {
+            path.clone()
+        };
+        if target != path || content.is_some() {
+            changes.push(FileChange {
+                path,
+                new_path: target,
+                content,
+            });
+        }
+    }
+
+    Ok(changes)
+}
+
+// print moves and unified diffs of content changes
+pub fn print_changes(changes: &[FileChange]) {
+    for change in changes.iter().filter(|c| c.is_move()) {
+        println!(
+            "  {} {} {} {}",
+            style("↪").dim(),
+            style(&change.path).red(),
+            style("→").dim(),
+            style(&change.new_path).green()
+        );
+    }
+
+    for change in changes {
+        let Some((before, after)) = &change.content else {
+            continue;
+        };
+
+        print_diff(&change.path, &change.new_path, before, after);
+    }
+}
+
+// print a colored unified diff between two versions of a file
+pub fn print_diff(old_label: &str, new_label: &str, before: &str, after: &str) {
+    println!();
+    println!("{}", style(format!("--- {}", old_label)).bold());
+    println!("{}", style(format!("+++ {}", new_label)).bold());
+    let diff = TextDiff::from_lines(before, after);
+    for hunk in diff.unified_diff().context_radius(1).iter_hunks() {
+        println!("{}", style(hunk.header()).cyan());
+        for line in hunk.iter_changes() {
+            let (sign, line_style) = match line.tag() {
+                ChangeTag::Delete => ("-", console::Style::new().red()),
+                ChangeTag::Insert => ("+", console::Style::new().green()),
+                ChangeTag::Equal => (" ", console::Style::new().dim()),
+            };
+            let text = line.to_string_lossy();
+            println!(
+                "{}",
+                line_style.apply_to(format!("{}{}", sign, text.trim_end_matches('\n')))
+            );
+        }
+    }
+}
+
+// apply planned changes to a pack folder, removing folders that moves leave empty
+pub fn apply_changes(root: &Path, changes: &[FileChange]) -> Result<()> {
+    for change in changes {
+        let source = root.join(&change.path);
+        let target = root.join(&change.new_path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+
+        match &change.content {
+            Some((_, after)) => {
+                fs::write(&target, after)
+                    .with_context(|| format!("Failed to write {}", change.new_path))?;
+                if change.is_move() {
+                    fs::remove_file(&source)
+                        .with_context(|| format!("Failed to remove {}", change.path))?;
+                }
+            }
+            None if change.is_move() => fs::rename(&source, &target).with_context(|| {
+                format!("Failed to move {} to {}", change.path, change.new_path)
+            })?,
+            None => {}
+        }
+    }
+
+    for change in changes.iter().filter(|c| c.is_move()) {
+        remove_empty_parents(root, &root.join(&change.path))?;
+    }
+
+    Ok(())
+}
+
+// remove the folders containing a deleted file for as long as they are empty
+pub fn remove_empty_parents(root: &Path, path: &Path) -> Result<()> {
+    let mut dir = path.parent();
+    while let Some(current) = dir {
+        if current == root || !current.starts_with(root) {
+            break;
+        }
+        let is_empty = match fs::read_dir(current) {
+            Ok(mut entries) => entries.next().is_none(),
+            Err(_) => false,
+        };
+        if !is_empty {
+            break;
+        }
+        fs::remove_dir(current)
+            .with_context(|| format!("Failed to remove {}", current.display()))?;
+        dir = current.parent();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_namespace_at_reference_boundaries() {
+        let content = r##"{"values": ["old:a", "#old:tag", "gold:b", "minecraft:old"]}"##;
+        assert_eq!(
+            replace_namespace(content, "old", "new"),
+            r##"{"values": ["new:a", "#new:tag", "gold:b", "minecraft:old"]}"##
+        );
+        assert_eq!(
+            replace_namespace("function old:a\nexecute run function old:b/c", "old", "new"),
+            "function new:a\nexecute run function new:b/c"
+        );
+    }
+
+    #[test]
+    fn replaces_namespace_in_data_paths() {
+        assert_eq!(
+            replace_namespace_in_path("data/old/function/a.mcfunction", "old", "new"),
+            "data/new/function/a.mcfunction"
+        );
+        assert_eq!(
+            replace_namespace_in_path("overlay_1/data/old/function/a.mcfunction", "old", "new"),
+            "overlay_1/data/new/function/a.mcfunction"
+        );
+        // only the namespace folder is renamed, not folders or files named like it
+        assert_eq!(
+            replace_namespace_in_path("data/other/function/old/data/old.json", "old", "new"),
+            "data/other/function/old/data/old.json"
+        );
+        assert_eq!(
+            replace_namespace_in_path("pack.mcmeta", "old", "new"),
+            "pack.mcmeta"
+        );
+    }
+
+    #[test]
+    fn replaces_whole_resource_locations_only() {
+        let content = "function ns:a/b\nfunction ns:a/bc\nfunction ns:a/b/c\nfunction other_ns:a/b";
+        assert_eq!(
+            replace_resource_location(content, "ns:a/b", "ns:moved", false),
+            "function ns:moved\nfunction ns:a/bc\nfunction ns:a/b/c\nfunction other_ns:a/b"
+        );
+    }
+
+    #[test]
+    fn matches_tag_references_by_prefix() {
+        let content = r##"{"values": ["ns:a", "#ns:a"]}"##;
+        assert_eq!(
+            replace_resource_location(content, "ns:a", "ns:b", false),
+            r##"{"values": ["ns:b", "#ns:a"]}"##
+        );
+        assert_eq!(
+            replace_resource_location(content, "ns:a", "ns:b", true),
+            r##"{"values": ["ns:a", "#ns:b"]}"##
+        );
+    }
+
+    #[test]
+    fn rewrites_only_references_to_the_moved_registry() {
+        let content = "advancement revoke @s only new:reward\n\
+                       function new:reward\n\
+                       execute if predicate new:reward run function new:reward\n\
+                       schedule clear new:reward\n\
+                       tellraw @a {\"text\":\"x\",\"click_event\":{\"action\":\"run_command\",\"command\":\"/function new:reward\"}}\n";
+        assert_eq!(
+            replace_element_references(
+                "data/new/function/a.mcfunction",
+                content,
+                "function",
+                "new:reward",
+                "new:moved"
+            ),
+            "advancement revoke @s only new:reward\n\
+             function new:moved\n\
+             execute if predicate new:reward run function new:moved\n\
+             schedule clear new:moved\n\
+             tellraw @a {\"text\":\"x\",\"click_event\":{\"action\":\"run_command\",\"command\":\"/function new:moved\"}}\n"
+        );
+        assert_eq!(
+            replace_element_references(
+                "data/new/function/a.mcfunction",
+                content,
+                "advancement",
+                "new:reward",
+                "new:moved"
+            )
+            .lines()
+            .next(),
+            Some("advancement revoke @s only new:moved")
+        );
+    }
+
+    #[test]
+    fn tells_json_references_apart_by_field() {
+        let advancement =
+            r#"{"rewards": {"recipes": ["new:a", "new:reward"], "function": "new:reward"}}"#;
+        let path = "data/new/advancement/reward.json";
+        assert_eq!(
+            replace_element_references(path, advancement, "function", "new:reward", "new:moved"),
+            r#"{"rewards": {"recipes": ["new:a", "new:reward"], "function": "new:moved"}}"#
+        );
+        assert_eq!(
+            replace_element_references(path, advancement, "recipe", "new:reward", "new:moved"),
+            r#"{"rewards": {"recipes": ["new:a", "new:moved"], "function": "new:reward"}}"#
+        );
+    }
+
+    #[test]
+    fn tells_tag_entries_apart_by_tag_registry() {
+        let tag = r##"{"values": ["new:reward", "#new:reward"]}"##;
+        assert_eq!(
+            replace_element_references(
+                "data/new/tags/function/all.json",
+                tag,
+                "function",
+                "new:reward",
+                "new:moved"
+            ),
+            r##"{"values": ["new:moved", "#new:reward"]}"##
+        );
+        assert_eq!(
+            replace_element_references(
+                "data/new/tags/function/all.json",
+                tag,
+                "tags/function",
+                "new:reward",
+                "new:moved"
+            ),
+            r##"{"values": ["new:reward", "#new:moved"]}"##
+        );
+        assert_eq!(
+            replace_element_references(
+                "data/new/tags/item/all.json",
+                tag,
+                "function",
+                "new:reward",
+                "new:moved"
+            ),
+            tag
+        );
+    }
+}
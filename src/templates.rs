@@ -0,0 +1,95 @@
+use crate::config;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// folder in the pack root holding project-only mcpack data such as templates
+pub const PROJECT_DATA_DIR: &str = ".mcpack";
+
+// templates live in <dir>/templates/<element>/<name><extension>
+pub const TEMPLATES_DIR: &str = "templates";
+
+// values substituted into {{placeholders}} when a template is used
+pub struct TemplateContext<'a> {
+    pub namespace: &'a str,
+    // path of the new element inside its registry folder, e.g. "utils/helper"
+    pub path: &'a str,
+}
+
+// template directories to search, the project's before the user's
+fn template_dirs(pack_root: Option<&Path>) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(root) = pack_root {
+        dirs.push(root.join(PROJECT_DATA_DIR).join(TEMPLATES_DIR));
+    }
+    if let Some(dir) = config::user_config_dir() {
+        dirs.push(dir.join(TEMPLATES_DIR));
+    }
+    dirs
+}
+
+// names of every template available for an element type, without duplicates
+pub fn list_templates(pack_root: Option<&Path>, element: &str, extension: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    for dir in template_dirs(pack_root) {
+        let Ok(entries) = fs::read_dir(dir.join(element)) else {
+            continue;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if let Some(name) = file_name.strip_suffix(extension) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+// read a template by name, with project templates shadowing user templates of the same name
+pub fn load_template(
+    pack_root: Option<&Path>,
+    element: &str,
+    extension: &str,
+    name: &str,
+) -> Result<String> {
+    // a name must not reach outside the templates folder
+    if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+        anyhow::bail!(
+            "Invalid template name '{}'. Template names can't contain path separators or '..'",
+            name
+        );
+    }
+
+    let file_name = format!("{}{}", name, extension);
+    let Some(path) = template_dirs(pack_root)
+        .into_iter()
+        .map(|dir| dir.join(element).join(&file_name))
+        .find(|path| path.is_file())
+    else {
+        let available = list_templates(pack_root, element, extension);
+        anyhow::bail!(
+            "Template '{}' not found for element '{}'. Available templates: {}",
+            name,
+            element,
+            if available.is_empty() {
+                "none".to_string()
+            } else {
+                available.join(", ")
+            }
+        );
+    };
+
+    fs::read_to_string(&path).with_context(|| format!("Failed to read template {}", path.display()))
+}
+
+// fill in {{namespace}}, {{name}}, {{path}} and {{id}} placeholders
+pub fn render(content: &str, context: &TemplateContext) -> String {
+    let name = context.path.rsplit('/').next().unwrap_or(context.path);
+    content
+        .replace("{{namespace}}", context.namespace)
+        .replace("{{name}}", name)
+        .replace("{{path}}", context.path)
+        .replace("{{id}}", &format!("{}:{}", context.namespace, context.path))
+}
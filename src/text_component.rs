@@ -0,0 +1,36 @@
+use serde_json::Value;
+
+// call `visit` with every piece of text in a description or chat component, in order, along
+// with its color (inherited from the parent component when it doesn't set one)
+pub fn visit_text(
+    component: &Value,
+    color: Option<&str>,
+    visit: &mut impl FnMut(&str, Option<&str>),
+) {
+    match component {
+        Value::String(s) => visit(s, color),
+        Value::Array(arr) => {
+            for component in arr {
+                visit_text(component, color, visit);
+            }
+        }
+        Value::Object(obj) => {
+            let color = obj.get("color").and_then(|c| c.as_str()).or(color);
+            if let Some(text) = obj.get("text").and_then(|t| t.as_str()) {
+                visit(text, color);
+            }
+            if let Some(extra) = obj.get("extra") {
+                visit_text(extra, color, visit);
+            }
+        }
+        Value::Number(_) | Value::Bool(_) => visit(&component.to_string(), color),
+        Value::Null => {}
+    }
+}
+
+// flatten a description or chat component into plain text, without any styling
+pub fn plain_text(component: &Value) -> String {
+    let mut text = String::new();
+    visit_text(component, None, &mut |piece, _| text.push_str(piece));
+    text
+}
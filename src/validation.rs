@@ -0,0 +1,237 @@
+use crate::pack::Pack;
+use crate::pack_formats;
+use crate::references::{self, PackIndex};
+use crate::registries;
+use crate::resource_location;
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+// a single problem found in a datapack, tied to the file it was found in
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub path: String,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn error(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Diagnostic {
+            severity: Severity::Error,
+            path: path.into(),
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn warning(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Diagnostic {
+            severity: Severity::Warning,
+            path: path.into(),
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    // file path including the line number when one is known
+    pub fn location(&self) -> String {
+        match self.line {
+            Some(line) => format!("{}:{}", self.path, line),
+            None => self.path.clone(),
+        }
+    }
+}
+
+// run every structural and cross-reference check against a pack
+pub fn validate_pack(pack: &mut Pack) -> Result<Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+
+    validate_pack_mcmeta(pack, &mut diagnostics)?;
+    let legacy_folders = references::legacy_folders(pack)?;
+
+    let files = pack.files().to_vec();
+    for path in &files {
+        if let Some(data_path) = path.strip_prefix("data/") {
+            validate_data_path(path, data_path, &legacy_folders, &mut diagnostics);
+        }
+
+        if path.ends_with(".json") {
+            validate_json(pack, path, &mut diagnostics)?;
+        }
+    }
+
+    let index = PackIndex::build(pack, legacy_folders);
+    diagnostics.extend(references::check_function_calls(pack, &index)?);
+    diagnostics.extend(references::check_function_tags(pack, &index)?);
+
+    diagnostics.sort_by(|a, b| {
+        a.path
+            .cmp(&b.path)
+            .then(a.line.cmp(&b.line))
+            .then(b.severity.cmp(&a.severity))
+    });
+    Ok(diagnostics)
+}
+
+fn validate_pack_mcmeta(pack: &mut Pack, diagnostics: &mut Vec<Diagnostic>) -> Result<()> {
+    let path = "pack.mcmeta";
+    if !pack.contains(path) {
+        diagnostics.push(Diagnostic::error(path, "pack.mcmeta not found"));
+        return Ok(());
+    }
+
+    let mcmeta: Value = match serde_json::from_slice(&pack.read(path)?) {
+        Ok(value) => value,
+        Err(err) => {
+            diagnostics.push(Diagnostic::error(path, format!("invalid JSON: {}", err)));
+            return Ok(());
+        }
+    };
+
+    let Some(pack_obj) = mcmeta.get("pack").filter(|p| p.is_object()) else {
+        diagnostics.push(Diagnostic::error(path, "missing 'pack' object"));
+        return Ok(());
+    };
+
+    if pack_obj.get("description").is_none() {
+        diagnostics.push(Diagnostic::warning(path, "missing 'pack.description'"));
+    }
+
+    if pack_obj.get("min_format").is_some() != pack_obj.get("max_format").is_some() {
+        diagnostics.push(Diagnostic::error(
+            path,
+            "missing 'pack.min_format' or 'pack.max_format'",
+        ));
+        return Ok(());
+    }
+
+    let declared = [
+        "min_format",
+        "max_format",
+        "supported_formats",
+        "pack_format",
+    ]
+    .iter()
+    .any(|key| pack_obj.get(key).is_some());
+    match pack_formats::format_range_from_pack(pack_obj) {
+        Some(range) => {
+            if pack_formats::cmp_format(range.min, range.max) == std::cmp::Ordering::Greater {
+                diagnostics.push(Diagnostic::error(
+                    path,
+                    format!(
+                        "minimum pack format {} is greater than maximum pack format {}",
+                        pack_formats::format_to_string(range.min),
+                        pack_formats::format_to_string(range.max)
+                    ),
+                ));
+            }
+            let mut formats = vec![range.min, range.max];
+            formats.dedup();
+            for format in formats {
+                if !pack_formats::is_supported_format(format) {
+                    diagnostics.push(Diagnostic::warning(
+                        path,
+                        format!(
+                            "pack format {} is not a known Minecraft version",
+                            pack_formats::format_to_string(format)
+                        ),
+                    ));
+                }
+            }
+            // versions before the decimal scheme only read pack_format
+            if pack_formats::is_legacy_format(range.min) && pack_obj.get("pack_format").is_none() {
+                diagnostics.push(Diagnostic::warning(
+                    path,
+                    format!(
+                        "missing 'pack.pack_format', which versions before pack format {} need to load the pack",
+                        pack_formats::format_to_string(pack_formats::FIRST_DECIMAL_FORMAT)
+                    ),
+                ));
+            }
+        }
+        None if !declared => diagnostics.push(Diagnostic::error(
+            path,
+            "missing pack format: expected 'pack.min_format' and 'pack.max_format', or 'pack.pack_format'",
+        )),
+        None => diagnostics.push(Diagnostic::error(
+            path,
+            "invalid pack format: formats must be integers or [major, minor] arrays, and 'pack.supported_formats' an integer, [min, max] array or {min_inclusive, max_inclusive} object",
+        )),
+    }
+
+    Ok(())
+}
+
+// check namespace, resource location characters and registry folder of a file in data/
+fn validate_data_path(
+    path: &str,
+    data_path: &str,
+    legacy_folders: &BTreeMap<String, String>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let Some((namespace, relative)) = data_path.split_once('/') else {
+        diagnostics.push(Diagnostic::warning(
+            path,
+            "file is not inside a namespace folder and will be ignored",
+        ));
+        return;
+    };
+
+    if let Some(c) = resource_location::invalid_namespace_char(namespace) {
+        diagnostics.push(Diagnostic::error(
+            path,
+            format!("illegal character '{}' in namespace '{}'", c, namespace),
+        ));
+    }
+
+    let relative = &*registries::current_folder_path(relative, legacy_folders);
+    let Some(registry) = registries::registry_for_path(relative) else {
+        let folder = relative.split('/').next().unwrap_or(relative);
+        let message = if relative.contains('/') {
+            format!("unknown registry folder '{}'", folder)
+        } else {
+            "file is not inside a registry folder and will be ignored".to_string()
+        };
+        diagnostics.push(Diagnostic::warning(path, message));
+        return;
+    };
+
+    let element_path = &relative[registry.len() + 1..];
+    if let Some(c) = resource_location::invalid_path_char(element_path) {
+        diagnostics.push(Diagnostic::error(
+            path,
+            format!("illegal character '{}' in resource location path", c),
+        ));
+    }
+
+    let extension = registries::extension_for_registry(&registry);
+    if !element_path.ends_with(extension) {
+        diagnostics.push(Diagnostic::warning(
+            path,
+            format!(
+                "files in '{}' must end with '{}' and this one will be ignored",
+                registry, extension
+            ),
+        ));
+    }
+}
+
+fn validate_json(pack: &mut Pack, path: &str, diagnostics: &mut Vec<Diagnostic>) -> Result<()> {
+    if let Err(err) = serde_json::from_slice::<Value>(&pack.read(path)?) {
+        diagnostics.push(Diagnostic::error(path, format!("invalid JSON: {}", err)));
+    }
+    Ok(())
+}